@@ -5,4 +5,9 @@ pub const VENDOR_COLLECTION_SEED: &[u8] = b"vendor_collection";
 pub const EQUIPMENT_SEED: &[u8] = b"equipment";
 pub const EQUIPMENT_ASSET_SEED: &[u8] = b"equipment_asset";
 pub const CONTRACT_SEED: &[u8] = b"bnpl_contract";
-pub const USDC_DECIMALS: u8 = 6;
\ No newline at end of file
+pub const UNIT_ASSET_SEED: &[u8] = b"unit_asset";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const USDC_DECIMALS: u8 = 6;
+pub const MAX_FEE_BPS: u16 = 2_000;
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const DEFAULT_AFTER_SECONDS: i64 = 90 * SECONDS_PER_DAY;
\ No newline at end of file
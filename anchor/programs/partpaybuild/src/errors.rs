@@ -118,4 +118,126 @@ pub enum ErrorCode {
     NoFundedUnitsAvailable,
     #[msg("Unauthorized action")]
     Unauthorized,
+    #[msg("Too many funders for this equipment")]
+    TooManyFunders,
+    #[msg("Borrower profile and credit score are required for funded units")]
+    BorrowerProfileRequired,
+    #[msg("Credit score is below the funder's minimum")]
+    CreditScoreTooLow,
+    #[msg("Borrower has more loans than the funder allows")]
+    TooManyLoans,
+    #[msg("A valid KYC attestation is required by the funder")]
+    KycAttestationRequired,
+    #[msg("Funded units are reserved for another borrower")]
+    FunderReservedForBorrower,
+    #[msg("Total amount must equal the price plus the funder's return")]
+    FunderTotalMismatch,
+    #[msg("Funding offer has expired")]
+    OfferExpired,
+    #[msg("Funding offer is still active")]
+    OfferStillActive,
+    #[msg("Funding offer does not cover this equipment")]
+    OfferNotApplicable,
+    #[msg("Equipment price exceeds the offer's maximum unit price")]
+    OfferPriceExceeded,
+    #[msg("Vendor is not active")]
+    VendorNotActive,
+    #[msg("Invalid vendor status transition")]
+    InvalidVendorStatus,
+    #[msg("Vendor already belongs to a marketplace")]
+    VendorAlreadyInMarketplace,
+    #[msg("Marketplace application is not pending")]
+    ApplicationNotPending,
+    #[msg("Marketplace account is required for this vendor")]
+    MarketplaceRequired,
+    #[msg("Fee exceeds the maximum allowed")]
+    FeeTooHigh,
+    #[msg("Invalid marketplace treasury")]
+    InvalidTreasury,
+    #[msg("Insufficient treasury balance")]
+    InsufficientTreasuryBalance,
+    #[msg("This operation is paused")]
+    OperationPaused,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("Only the program admin can do this")]
+    InvalidAdmin,
+    #[msg("No pending admin transfer for this key")]
+    InvalidPendingAdmin,
+    #[msg("Mint is not on the allowlist")]
+    MintNotAllowed,
+    #[msg("Mint allowlist is full")]
+    MintAllowlistFull,
+    #[msg("Amount received after transfer fees is insufficient")]
+    InsufficientFunds,
+    #[msg("Price feed account does not match the currency feed")]
+    InvalidPriceFeed,
+    #[msg("Price feed is stale")]
+    PriceFeedStale,
+    #[msg("Price feed confidence interval is too wide")]
+    PriceFeedConfidence,
+    #[msg("Currency feed and price feed accounts are required")]
+    CurrencyFeedRequired,
+    #[msg("Equipment priced in a reference currency cannot be funded")]
+    ReferencePricedFunding,
+    #[msg("Signer is not the vendor authority")]
+    InvalidVendorAuthority,
+    #[msg("Signer is not the pending vendor authority")]
+    InvalidPendingAuthority,
+    #[msg("Invalid delegate permissions")]
+    InvalidDelegatePermissions,
+    #[msg("Too many catalog delegates")]
+    TooManyDelegates,
+    #[msg("Signer lacks the vendor permission for this action")]
+    MissingVendorPermission,
+    #[msg("Stock cannot drop below sold and funded units")]
+    StockBelowCommitted,
+    #[msg("Equipment is delisted")]
+    EquipmentDelisted,
+    #[msg("Equipment still has active contracts or unreleased escrows")]
+    EquipmentNotSettled,
+    #[msg("Funder position is already on the current terms")]
+    TermsUpToDate,
+    #[msg("Equipment variant does not exist")]
+    InvalidVariant,
+    #[msg("Too many variants on this equipment")]
+    TooManyVariants,
+    #[msg("Category slug must be 1 to 32 lowercase letters, digits or dashes")]
+    InvalidCategorySlug,
+    #[msg("Category does not belong to the vendor's marketplace")]
+    CategoryNotInMarketplace,
+    #[msg("Too many tags or a tag is too long")]
+    InvalidTags,
+    #[msg("Category accounts do not match the equipment")]
+    InvalidCategory,
+    #[msg("Listing only accepts full payment")]
+    FullPaymentOnly,
+    #[msg("Listing only accepts BNPL")]
+    BnplOnly,
+    #[msg("Order can no longer be cancelled")]
+    OrderNotCancellable,
+    #[msg("Installments have already been paid on this order")]
+    OrderHasPayments,
+    #[msg("Signer cannot resolve disputes for this vendor")]
+    InvalidDisputeResolver,
+    #[msg("Cart must have between one and the maximum number of lines")]
+    InvalidCartLines,
+    #[msg("Cart listings must all belong to the cart's vendor")]
+    CartVendorMismatch,
+    #[msg("Cart total is below the listed prices")]
+    CartTotalTooLow,
+    #[msg("Cart line has already been delivered")]
+    CartLineDelivered,
+    #[msg("Equipment priced in a reference currency cannot be bought in a cart")]
+    CartReferencePriced,
+    #[msg("Quantity must be at least one")]
+    InvalidQuantity,
+    #[msg("Unit count must be between one and the units left to deliver")]
+    InvalidUnitCount,
+    #[msg("Unit asset account does not match the unit being delivered")]
+    InvalidUnitAsset,
+    #[msg("Contract total is below the listed price")]
+    ContractTotalTooLow,
+    #[msg("Credit score account is required for a borrower with a profile")]
+    CreditScoreRequired,
 }
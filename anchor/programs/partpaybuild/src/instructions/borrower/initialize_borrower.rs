@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
-use crate::state::{
-    borrower::Borrower,
-    credit_score::CreditScore
+use crate::{
+    errors::ErrorCode,
+    state::{
+        borrower::Borrower,
+        config::ProgramConfig,
+        credit_score::CreditScore,
+    },
 };
 
 #[derive(Accounts)]
@@ -25,6 +29,12 @@ pub struct InitializeBorrower<'info> {
     )]
     pub credit_score: Account<'info, CreditScore>,
     pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_CONTRACTS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
 }
 
 pub fn initialize_borrower(ctx: Context<InitializeBorrower>) -> Result<()> {
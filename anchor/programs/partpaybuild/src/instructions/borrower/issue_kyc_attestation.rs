@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{config::ProgramConfig, kyc::KycAttestation},
+    errors::ErrorCode,
+};
+
+#[derive(Accounts)]
+#[instruction(subject: Pubkey)]
+pub struct IssueKycAttestation<'info> {
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        space = KycAttestation::LEN,
+        seeds = [KycAttestation::SEED_PREFIX, attestor.key().as_ref(), subject.as_ref()],
+        bump
+    )]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_CONTRACTS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn issue_kyc_attestation(
+    ctx: Context<IssueKycAttestation>,
+    subject: Pubkey,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(expiry) = expires_at {
+        require!(expiry > now, ErrorCode::InvalidTimestamp);
+    }
+
+    let attestation = &mut ctx.accounts.kyc_attestation;
+    attestation.attestor = ctx.accounts.attestor.key();
+    attestation.subject = subject;
+    attestation.issued_at = now;
+    attestation.expires_at = expires_at;
+    attestation.bump = ctx.bumps.kyc_attestation;
+
+    msg!("KYC attestation issued for {}", subject);
+    Ok(())
+}
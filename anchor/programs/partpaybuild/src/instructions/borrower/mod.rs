@@ -1,7 +1,9 @@
-mod track_repayment;
 mod initialize_borrower;
 mod view_credit_score;
+mod issue_kyc_attestation;
+mod revoke_kyc_attestation;
 
 pub use initialize_borrower::*;
-pub use track_repayment::*;
-pub use view_credit_score::*;
\ No newline at end of file
+pub use view_credit_score::*;
+pub use issue_kyc_attestation::*;
+pub use revoke_kyc_attestation::*;
\ No newline at end of file
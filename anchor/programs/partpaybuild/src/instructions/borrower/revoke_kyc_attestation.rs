@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::kyc::KycAttestation;
+
+#[derive(Accounts)]
+pub struct RevokeKycAttestation<'info> {
+    #[account(
+        mut,
+        close = attestor,
+        has_one = attestor,
+        seeds = [KycAttestation::SEED_PREFIX, attestor.key().as_ref(), kyc_attestation.subject.as_ref()],
+        bump = kyc_attestation.bump
+    )]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+}
+
+pub fn revoke_kyc_attestation(ctx: Context<RevokeKycAttestation>) -> Result<()> {
+    msg!("KYC attestation revoked for {}", ctx.accounts.kyc_attestation.subject);
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::config::ProgramConfig,
+};
+use super::UpdateConfig;
+
+pub fn add_allowed_mint(ctx: Context<UpdateConfig>, mint: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    if config.is_mint_allowed(&mint) {
+        return Ok(());
+    }
+    require!(
+        config.allowed_mints.len() < ProgramConfig::MAX_ALLOWED_MINTS,
+        ErrorCode::MintAllowlistFull
+    );
+    config.allowed_mints.push(mint);
+    msg!("Mint allowed: {}", mint);
+    Ok(())
+}
+
+/// Existing listings and contracts keep their pinned mint; only new listings,
+/// fundings and contracts are refused.
+pub fn remove_allowed_mint(ctx: Context<UpdateConfig>, mint: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(config.is_mint_allowed(&mint), ErrorCode::MintNotAllowed);
+    config.allowed_mints.retain(|allowed| allowed != &mint);
+    msg!("Mint removed: {}", mint);
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::{config::ProgramConfig, currency::CurrencyFeed},
+};
+
+#[derive(Accounts)]
+#[instruction(currency: [u8; 3], mint: Pubkey)]
+pub struct SetCurrencyFeed<'info> {
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::InvalidAdmin,
+        constraint = config.is_mint_allowed(&mint) @ ErrorCode::MintNotAllowed
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = CurrencyFeed::LEN,
+        seeds = [CurrencyFeed::SEED_PREFIX, currency.as_ref(), mint.as_ref()],
+        bump
+    )]
+    pub currency_feed: Account<'info, CurrencyFeed>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_currency_feed(
+    ctx: Context<SetCurrencyFeed>,
+    currency: [u8; 3],
+    mint: Pubkey,
+    decimals: u8,
+    price_feed: Pubkey,
+    max_staleness_seconds: i64,
+    max_confidence_bps: u16,
+) -> Result<()> {
+    require!(max_staleness_seconds > 0, ErrorCode::InvalidDuration);
+    require!(
+        max_confidence_bps as u128 <= CurrencyFeed::BPS_DENOMINATOR,
+        ErrorCode::InvalidAmount
+    );
+
+    let currency_feed = &mut ctx.accounts.currency_feed;
+    currency_feed.currency = currency;
+    currency_feed.mint = mint;
+    currency_feed.decimals = decimals;
+    currency_feed.price_feed = price_feed;
+    currency_feed.max_staleness_seconds = max_staleness_seconds;
+    currency_feed.max_confidence_bps = max_confidence_bps;
+    currency_feed.bump = ctx.bumps.currency_feed;
+    msg!("Currency feed set to {}", price_feed);
+    Ok(())
+}
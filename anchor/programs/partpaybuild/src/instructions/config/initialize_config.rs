@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    program::Partpay,
+    state::config::ProgramConfig,
+};
+
+/// Only the program's upgrade authority can create the config, so the first
+/// admin cannot be front-run.
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProgramConfig::LEN,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Partpay>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::InvalidAdmin)]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.pending_admin = None;
+    config.paused = 0;
+    config.allowed_mints = Vec::new();
+    config.bump = ctx.bumps.config;
+
+    msg!("Program config initialized with admin {}", config.admin);
+    Ok(())
+}
@@ -0,0 +1,11 @@
+mod initialize_config;
+mod set_paused;
+mod transfer_admin;
+mod allowed_mints;
+mod currency_feed;
+
+pub use initialize_config::*;
+pub use set_paused::*;
+pub use transfer_admin::*;
+pub use allowed_mints::*;
+pub use currency_feed::*;
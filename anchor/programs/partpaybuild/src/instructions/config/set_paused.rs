@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::config::ProgramConfig,
+};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub admin: Signer<'info>,
+}
+
+/// Replaces the whole pause bitmask; see the `ProgramConfig::PAUSE_*` flags.
+pub fn set_paused(ctx: Context<UpdateConfig>, paused: u8) -> Result<()> {
+    require!(paused & !ProgramConfig::PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags);
+    ctx.accounts.config.paused = paused;
+    msg!("Pause flags set to {:#07b}", paused);
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::config::ProgramConfig,
+};
+use super::UpdateConfig;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(new_admin.key()) @ ErrorCode::InvalidPendingAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub new_admin: Signer<'info>,
+}
+
+pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.config.pending_admin = Some(new_admin);
+    msg!("Admin transfer proposed to {}", new_admin);
+    Ok(())
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.new_admin.key();
+    config.pending_admin = None;
+    msg!("Admin transferred to {}", config.admin);
+    Ok(())
+}
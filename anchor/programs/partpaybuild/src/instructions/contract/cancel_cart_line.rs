@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    errors::ErrorCode,
+    state::{
+        borrower::Borrower,
+        cart::CartContract,
+        config::ProgramConfig,
+        delegate::VendorDelegate,
+        equipment::{DeliveryStatus, Equipment},
+        vendor::Vendor,
+    },
+    utils::transfer::transfer_checked_received,
+};
+
+#[derive(Accounts)]
+#[instruction(line_index: u8)]
+pub struct CancelCartLine<'info> {
+    #[account(
+        mut,
+        has_one = vendor,
+        seeds = [CartContract::SEED_PREFIX, cart.borrower.as_ref(), cart.cart_unique_id.as_ref()],
+        bump = cart.bump
+    )]
+    pub cart: Box<Account<'info, CartContract>>,
+    #[account(
+        mut,
+        constraint = cart
+            .lines
+            .get(line_index as usize)
+            .is_some_and(|line| line.equipment == equipment.key()) @ ErrorCode::InvalidEquipment
+    )]
+    pub equipment: Box<Account<'info, Equipment>>,
+    pub vendor: Box<Account<'info, Vendor>>,
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = cart,
+        associated_token::token_program = token_program
+    )]
+    pub cart_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = cart.borrower
+    )]
+    pub borrower_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: The borrower's profile PDA; its loan count is released in logic
+    #[account(
+        mut,
+        seeds = [Borrower::SEED_PREFIX, cart.borrower.as_ref()],
+        bump
+    )]
+    pub borrower_profile: UncheckedAccount<'info>,
+    /// The borrower before shipment, or the vendor with shipping permission
+    /// until delivery.
+    pub authority: Signer<'info>,
+    pub vendor_delegate: Option<Account<'info, VendorDelegate>>,
+    #[account(address = cart.stablecoin_mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Cancels an undelivered cart line: its deposit share goes back to the
+/// borrower, its units go back on sale and the cart total drops by the
+/// line's share. Only possible before any installment beyond the deposit;
+/// cancelling the last open line closes the cart.
+pub fn cancel_cart_line(ctx: Context<CancelCartLine>, line_index: u8) -> Result<()> {
+    let cart = &ctx.accounts.cart;
+    let line = cart.lines[line_index as usize].clone();
+    let authority = ctx.accounts.authority.key();
+    let is_vendor = ctx.accounts.vendor.authorizes(
+        &ctx.accounts.vendor.key(),
+        &authority,
+        ctx.accounts.vendor_delegate.as_deref(),
+        VendorDelegate::SHIPPING,
+    );
+    let cancellable = match line.delivery_status {
+        DeliveryStatus::Pending => is_vendor || authority == cart.borrower,
+        DeliveryStatus::Shipped => is_vendor,
+        _ => false,
+    };
+    require!(cancellable && !cart.is_completed, ErrorCode::OrderNotCancellable);
+    require!(cart.paid_installments <= 1, ErrorCode::OrderHasPayments);
+
+    // The line's share of the total follows its listed value among the lines
+    // still open, so the shares of successive cancellations add up.
+    let line_value = line.unit_price as u128 * line.quantity as u128;
+    let open_value: u128 = cart
+        .lines
+        .iter()
+        .filter(|l| l.delivery_status != DeliveryStatus::Cancelled)
+        .map(|l| l.unit_price as u128 * l.quantity as u128)
+        .sum();
+    let last_open_line = open_value == line_value;
+    let total_share = if last_open_line {
+        cart.total_amount
+    } else {
+        (cart.total_amount as u128 * line_value / open_value) as u64
+    };
+
+    let borrower = cart.borrower;
+    let cart_unique_id = cart.cart_unique_id;
+    let bump = cart.bump;
+    if line.escrow_amount > 0 {
+        transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.cart_vault.to_account_info(),
+            &mut ctx.accounts.borrower_token_account,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.cart.to_account_info(),
+            &[&[
+                CartContract::SEED_PREFIX,
+                borrower.as_ref(),
+                cart_unique_id.as_ref(),
+                &[bump],
+            ]],
+            line.escrow_amount,
+        )?;
+    }
+
+    let equipment = &mut ctx.accounts.equipment;
+    let sku = equipment.variant_mut(line.variant)?;
+    sku.sold_quantity = sku.sold_quantity.saturating_sub(line.quantity);
+    equipment.sold_quantity = equipment.sold_quantity.saturating_sub(line.quantity);
+    equipment.active_contracts = equipment.active_contracts.saturating_sub(1);
+    equipment.open_escrows = equipment.open_escrows.saturating_sub(1);
+    equipment.refresh_status();
+
+    let cart = &mut ctx.accounts.cart;
+    cart.lines[line_index as usize].delivery_status = DeliveryStatus::Cancelled;
+    cart.total_amount = cart.total_amount.saturating_sub(total_share);
+    cart.amount_paid = cart.amount_paid.saturating_sub(line.escrow_amount);
+    cart.deposit = cart.deposit.saturating_sub(line.escrow_amount);
+    if last_open_line {
+        cart.is_completed = true;
+        Borrower::close_loan(&ctx.accounts.borrower_profile, ctx.program_id, &mut cart.counts_toward_loans)?;
+    }
+    msg!("Cart line {} cancelled", line_index);
+    Ok(())
+}
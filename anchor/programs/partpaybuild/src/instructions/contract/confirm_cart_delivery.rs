@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    errors::ErrorCode,
+    state::{
+        cart::CartContract,
+        config::ProgramConfig,
+        equipment::{DeliveryStatus, Equipment},
+    },
+    utils::transfer::transfer_checked_received,
+};
+
+#[derive(Accounts)]
+#[instruction(line_index: u8)]
+pub struct ConfirmCartDelivery<'info> {
+    #[account(
+        mut,
+        has_one = borrower,
+        seeds = [CartContract::SEED_PREFIX, borrower.key().as_ref(), cart.cart_unique_id.as_ref()],
+        bump = cart.bump
+    )]
+    pub cart: Box<Account<'info, CartContract>>,
+    #[account(
+        mut,
+        constraint = cart
+            .lines
+            .get(line_index as usize)
+            .is_some_and(|line| line.equipment == equipment.key()) @ ErrorCode::InvalidEquipment
+    )]
+    pub equipment: Box<Account<'info, Equipment>>,
+    pub borrower: Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = cart,
+        associated_token::token_program = token_program
+    )]
+    pub cart_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = cart.vendor
+    )]
+    pub vendor_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = cart.stablecoin_mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Confirms delivery of one cart line and releases its share of the deposit
+/// to the vendor.
+pub fn confirm_cart_delivery(ctx: Context<ConfirmCartDelivery>, line_index: u8) -> Result<()> {
+    let line = &ctx.accounts.cart.lines[line_index as usize];
+    require!(
+        matches!(line.delivery_status, DeliveryStatus::Pending | DeliveryStatus::Shipped),
+        ErrorCode::CartLineDelivered
+    );
+    let amount = line.escrow_amount;
+
+    let borrower = ctx.accounts.cart.borrower;
+    let cart_unique_id = ctx.accounts.cart.cart_unique_id;
+    let bump = ctx.accounts.cart.bump;
+    if amount > 0 {
+        transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.cart_vault.to_account_info(),
+            &mut ctx.accounts.vendor_token_account,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.cart.to_account_info(),
+            &[&[
+                CartContract::SEED_PREFIX,
+                borrower.as_ref(),
+                cart_unique_id.as_ref(),
+                &[bump],
+            ]],
+            amount,
+        )?;
+    }
+
+    ctx.accounts.cart.lines[line_index as usize].delivery_status = DeliveryStatus::Delivered;
+    let equipment = &mut ctx.accounts.equipment;
+    equipment.open_escrows = equipment.open_escrows.saturating_sub(1);
+    msg!("Cart line {} delivered", line_index);
+    Ok(())
+}
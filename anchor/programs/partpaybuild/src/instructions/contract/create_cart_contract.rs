@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{
+    errors::ErrorCode,
+    state::{
+        borrower::Borrower,
+        cart::{CartContract, CartLine, CartLineRequest},
+        config::ProgramConfig,
+        contract::InstallmentFrequency,
+        equipment::{DeliveryStatus, Equipment},
+        marketplace::{FeeKind, Marketplace},
+        vendor::Vendor,
+    },
+    utils::{fees::marketplace_fee, transfer::transfer_checked_received},
+};
+
+/// Remaining accounts are the writable `Equipment` accounts of the requested
+/// lines, in order.
+#[derive(Accounts)]
+#[instruction(cart_unique_id: Pubkey)]
+pub struct CreateCartContract<'info> {
+    #[account(
+        init,
+        payer = buyer,
+        space = CartContract::LEN,
+        seeds = [CartContract::SEED_PREFIX, buyer.key().as_ref(), cart_unique_id.as_ref()],
+        bump
+    )]
+    pub cart: Box<Account<'info, CartContract>>,
+    #[account(constraint = vendor.is_active() @ ErrorCode::VendorNotActive)]
+    pub vendor: Box<Account<'info, Vendor>>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(constraint = config.is_mint_allowed(&usdc_mint.key()) @ ErrorCode::MintNotAllowed)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = buyer
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = cart,
+        associated_token::token_program = token_program
+    )]
+    pub cart_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: The buyer's borrower profile PDA; read and updated in logic once
+    /// it has been initialized
+    #[account(
+        mut,
+        seeds = [Borrower::SEED_PREFIX, buyer.key().as_ref()],
+        bump
+    )]
+    pub borrower: UncheckedAccount<'info>,
+    pub marketplace: Option<Box<Account<'info, Marketplace>>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_CONTRACTS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn create_cart_contract<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateCartContract<'info>>,
+    cart_unique_id: Pubkey,
+    lines: Vec<CartLineRequest>,
+    total_amount: u64,
+    deposit: u64,
+    installment_frequency: InstallmentFrequency,
+) -> Result<()> {
+    require!(
+        !lines.is_empty()
+            && lines.len() <= CartContract::MAX_LINES
+            && ctx.remaining_accounts.len() == lines.len(),
+        ErrorCode::InvalidCartLines
+    );
+    require!(total_amount > deposit, ErrorCode::InvalidAmount);
+    let frequency_seconds = installment_frequency.as_seconds();
+    require!(frequency_seconds > 0, ErrorCode::InvalidFrequency);
+
+    let now = Clock::get()?.unix_timestamp;
+    let vendor_key = ctx.accounts.vendor.key();
+    let mint_key = ctx.accounts.usdc_mint.key();
+    let mut listings: Vec<Account<'info, Equipment>> = Vec::new();
+    let mut cart_lines: Vec<CartLine> = Vec::with_capacity(lines.len());
+    let mut listed_total: u64 = 0;
+    let mut min_deposit: u64 = 0;
+    let mut duration = i64::MAX;
+
+    for (request, account_info) in lines.iter().zip(ctx.remaining_accounts.iter()) {
+        require!(request.quantity > 0, ErrorCode::InvalidAmount);
+        // Lines on the same listing share one loaded account so their stock
+        // changes add up.
+        let index = match listings.iter().position(|l| l.key() == *account_info.key) {
+            Some(index) => index,
+            None => {
+                listings.push(Account::<Equipment>::try_from(account_info)?);
+                listings.len() - 1
+            }
+        };
+        let equipment = &mut listings[index];
+        require!(equipment.vendor == vendor_key, ErrorCode::CartVendorMismatch);
+        require!(equipment.mint == mint_key, ErrorCode::InvalidMint);
+        require!(!equipment.delisted, ErrorCode::EquipmentDelisted);
+        require!(equipment.accepts_bnpl(now), ErrorCode::FullPaymentOnly);
+        require!(equipment.reference_currency.is_none(), ErrorCode::CartReferencePriced);
+
+        let sku = equipment.variant_mut(request.variant)?;
+        let new_sold = sku
+            .sold_quantity
+            .checked_add(request.quantity)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            new_sold <= sku.total_quantity - sku.funded_quantity,
+            ErrorCode::OutOfStock
+        );
+        sku.sold_quantity = new_sold;
+        let unit_price = sku.price;
+        let line_total = unit_price.checked_mul(request.quantity).ok_or(ErrorCode::MathOverflow)?;
+        listed_total = listed_total.checked_add(line_total).ok_or(ErrorCode::MathOverflow)?;
+        min_deposit = sku
+            .minimum_deposit
+            .checked_mul(request.quantity)
+            .and_then(|d| d.checked_add(min_deposit))
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        equipment.sold_quantity += request.quantity;
+        equipment.active_contracts += 1;
+        equipment.open_escrows += 1;
+        equipment.refresh_status();
+        duration = duration.min(equipment.max_duration_seconds);
+
+        cart_lines.push(CartLine {
+            equipment: equipment.key(),
+            variant: request.variant,
+            quantity: request.quantity,
+            equipment_unit_index: equipment.issue_units(request.quantity)?,
+            unit_price,
+            escrow_amount: 0,
+            delivery_status: DeliveryStatus::Pending,
+        });
+    }
+    require!(total_amount >= listed_total, ErrorCode::CartTotalTooLow);
+    require!(deposit >= min_deposit, ErrorCode::DepositBelowMinimum);
+
+    let installment_count = (duration / frequency_seconds) as u64;
+    require!(installment_count <= u8::MAX as u64, ErrorCode::TooManyInstallments);
+    if let Some(vendor_marketplace) = ctx.accounts.vendor.marketplace {
+        let marketplace = ctx
+            .accounts
+            .marketplace
+            .as_ref()
+            .filter(|m| m.key() == vendor_marketplace)
+            .ok_or(ErrorCode::MarketplaceRequired)?;
+        marketplace
+            .rules
+            .validate_contract(total_amount, deposit, duration, installment_count)?;
+    }
+
+    let fee = marketplace_fee(
+        &ctx.accounts.vendor,
+        ctx.accounts.marketplace.as_deref(),
+        ctx.accounts.treasury_token_account.as_deref(),
+        &mint_key,
+        FeeKind::Deposit,
+        deposit,
+    )?;
+    let received_deposit = transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.buyer_token_account.to_account_info(),
+        &mut ctx.accounts.cart_vault,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.buyer.to_account_info(),
+        &[],
+        deposit - fee,
+    )?;
+    let mut received_fee = 0;
+    if fee > 0 {
+        let treasury = ctx.accounts.treasury_token_account.as_mut().ok_or(ErrorCode::InvalidTreasury)?;
+        received_fee = transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.buyer_token_account.to_account_info(),
+            treasury,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.buyer.to_account_info(),
+            &[],
+            fee,
+        )?;
+    }
+
+    // Each line's share of the escrowed deposit follows its listed value; the
+    // last line takes the rounding remainder.
+    let mut allocated: u64 = 0;
+    let line_count = cart_lines.len();
+    for (i, line) in cart_lines.iter_mut().enumerate() {
+        line.escrow_amount = if i + 1 == line_count {
+            received_deposit - allocated
+        } else {
+            (received_deposit as u128 * (line.unit_price as u128 * line.quantity as u128)
+                / listed_total as u128) as u64
+        };
+        allocated += line.escrow_amount;
+    }
+
+    for listing in listings.iter() {
+        listing.exit(ctx.program_id)?;
+    }
+
+    let cart = &mut ctx.accounts.cart;
+    cart.borrower = ctx.accounts.buyer.key();
+    cart.vendor = vendor_key;
+    cart.cart_unique_id = cart_unique_id;
+    cart.stablecoin_mint = mint_key;
+    cart.lines = cart_lines;
+    cart.total_amount = total_amount;
+    cart.amount_paid = received_deposit + received_fee;
+    cart.deposit = deposit;
+    cart.start_date = now;
+    cart.end_date = now + duration;
+    cart.last_payment_date = now;
+    cart.installment_count = installment_count as u8;
+    cart.paid_installments = 1;
+    cart.installment_frequency = installment_frequency;
+    cart.is_completed = false;
+    cart.bump = ctx.bumps.cart;
+
+    cart.counts_toward_loans = Borrower::open_loan(&ctx.accounts.borrower, ctx.program_id)?;
+
+    msg!("Cart contract created with {} lines", cart.lines.len());
+    Ok(())
+}
@@ -1,16 +1,24 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount, Transfer},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use crate::{
     constants::CONTRACT_SEED,
     errors::ErrorCode,
     state::{
-        contract::{BNPLContract, InstallmentFrequency},
-        equipment::{DeliveryStatus, Equipment, EquipmentStatus},
+        config::ProgramConfig,
+        borrower::Borrower,
+        contract::{BNPLContract, ContractKind, InstallmentFrequency},
+        credit_score::CreditScore,
+        currency::{settlement_rate, CurrencyFeed},
+        equipment::{DeliveryStatus, Equipment},
         escrow::Escrow,
+        kyc::KycAttestation,
+        marketplace::{FeeKind, Marketplace},
+        vendor::Vendor,
     },
+    utils::{fees::marketplace_fee, transfer::transfer_checked_received, validation::validate_funder_criteria},
 };
 
 #[derive(Accounts)]
@@ -33,11 +41,24 @@ pub struct CreateContract<'info> {
     pub contract: Box<Account<'info, BNPLContract>>,
     #[account(mut)]
     pub equipment: Box<Account<'info, Equipment>>,
+    #[account(
+        constraint = vendor.key() == equipment.vendor @ ErrorCode::InvalidVendor,
+        constraint = vendor.is_active() @ ErrorCode::VendorNotActive
+    )]
+    pub vendor: Box<Account<'info, Vendor>>,
     #[account(mut)]
     pub buyer: Signer<'info>,
-    pub usdc_mint: Account<'info, Mint>,
-    #[account(mut)]
-    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        address = equipment.mint @ ErrorCode::InvalidMint,
+        constraint = config.is_mint_allowed(&usdc_mint.key()) @ ErrorCode::MintNotAllowed
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = buyer
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         init,
         payer = buyer,
@@ -50,17 +71,48 @@ pub struct CreateContract<'info> {
         init_if_needed,
         payer = buyer,
         associated_token::mint = usdc_mint,
-        associated_token::authority = escrow
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
     )]
-    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
-    #[account(mut)]
-    pub payee_token_account: Box<Account<'info, TokenAccount>>,
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = payee
+    )]
+    pub payee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account()]
     /// CHECK: Validated in logic
     pub payee: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+    /// CHECK: The buyer's borrower profile PDA; read and updated in logic once
+    /// it has been initialized
+    #[account(
+        mut,
+        seeds = [Borrower::SEED_PREFIX, buyer.key().as_ref()],
+        bump
+    )]
+    pub borrower: UncheckedAccount<'info>,
+    #[account(
+        seeds = [CreditScore::SEED_PREFIX, borrower.key().as_ref()],
+        bump = credit_score.bump
+    )]
+    pub credit_score: Option<Box<Account<'info, CreditScore>>>,
+    pub kyc_attestation: Option<Box<Account<'info, KycAttestation>>>,
+    pub marketplace: Option<Box<Account<'info, Marketplace>>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub currency_feed: Option<Box<Account<'info, CurrencyFeed>>>,
+    /// CHECK: Checked against `currency_feed.price_feed` before it is read
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_CONTRACTS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
 }
 
 pub fn create_contract(
@@ -71,91 +123,188 @@ pub fn create_contract(
     deposit: u64,
     insurance_premium: Option<u64>,
     funder_unique_id: Option<Pubkey>,
+    variant: u8,
+    quantity: u64,
 ) -> Result<()> {
     msg!("Starting create_contract");
     let equipment = &mut ctx.accounts.equipment;
     msg!("Equipment loaded: {:?}", equipment.key());
+    require!(!equipment.delisted, ErrorCode::EquipmentDelisted);
 
+    require!(quantity > 0, ErrorCode::InvalidQuantity);
     require!(total_amount > deposit, ErrorCode::InvalidAmount);
     let frequency_seconds = installment_frequency.as_seconds();
     require!(frequency_seconds > 0, ErrorCode::InvalidFrequency);
 
-    let vendor_quantity = equipment.total_quantity - equipment.funded_quantity;
+    let sku = equipment.variant(variant)?;
+    let vendor_quantity = sku.total_quantity - sku.funded_quantity;
     msg!("Vendor quantity: {}", vendor_quantity);
+    let buyer_key = ctx.accounts.buyer.key();
+    let now = Clock::get()?.unix_timestamp;
     let contract = &mut ctx.accounts.contract;
     let payee: Pubkey;
+    let mut funder_escrow: Option<Pubkey> = None;
     let min_deposit: u64;
     let duration: i64;
+    let unit_price: u64;
+    let terms_version: u32;
 
     match funder_unique_id {
         Some(funder_id) => {
             require!(
-                equipment.funded_quantity > equipment.funded_sold_quantity,
+                sku.funded_quantity - sku.funded_sold_quantity >= quantity,
                 ErrorCode::NoFundedUnitsAvailable
             );
             let funder_info = equipment
                 .funders
                 .iter_mut()
-                .find(|f| f.funder == funder_id && f.quantity > 0)
+                .find(|f| f.funder == funder_id && f.variant == variant && f.unsold_units() >= quantity)
                 .ok_or(ErrorCode::NoAvailableFunder)?;
+            if let Some(reserved_for) = funder_info.borrower {
+                require!(reserved_for == buyer_key, ErrorCode::FunderReservedForBorrower);
+            }
+            validate_funder_criteria(
+                &funder_info.criteria,
+                &buyer_key,
+                Borrower::load(&ctx.accounts.borrower, ctx.program_id)?.as_ref(),
+                ctx.accounts.credit_score.as_deref().map(|c| &**c),
+                ctx.accounts.kyc_attestation.as_deref().map(|k| &**k),
+                now,
+            )?;
+            let required_total = funder_info
+                .required_total(funder_info.unit_price)?
+                .checked_mul(quantity)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(total_amount == required_total, ErrorCode::FunderTotalMismatch);
             payee = funder_info.funder;
+            funder_escrow = Some(funder_info.escrow);
             min_deposit = funder_info.minimum_deposit;
             duration = funder_info.duration_seconds;
-            equipment.funded_sold_quantity += 1;
+            unit_price = funder_info.unit_price;
+            terms_version = funder_info.terms_version;
+            funder_info.record_sale(quantity, unit_price, true)?;
+            equipment.variant_mut(variant)?.funded_sold_quantity += quantity;
+            equipment.funded_sold_quantity += quantity;
             msg!("Using funder as payee: {:?}", payee);
         }
         None => {
             msg!("Processing vendor equipment");
+            // Funded units were financed in time; only vendor stock is held
+            // to the listing's payment preference.
+            require!(equipment.accepts_bnpl(now), ErrorCode::FullPaymentOnly);
             require!(
-                equipment.sold_quantity < vendor_quantity,
+                vendor_quantity.saturating_sub(sku.sold_quantity) >= quantity,
                 ErrorCode::OutOfStock
             );
+            let listed_total = sku.price.checked_mul(quantity).ok_or(ErrorCode::MathOverflow)?;
+            require!(total_amount >= listed_total, ErrorCode::ContractTotalTooLow);
             payee = equipment.vendor;
-            min_deposit = equipment.minimum_deposit;
+            min_deposit = sku.minimum_deposit;
             duration = equipment.max_duration_seconds;
-            equipment.sold_quantity += 1;
+            unit_price = sku.price;
+            terms_version = equipment.terms_version;
+            equipment.variant_mut(variant)?.sold_quantity += quantity;
+            equipment.sold_quantity += quantity;
             msg!("Using vendor as payee: {:?}", payee);
         }
     }
 
     require!(ctx.accounts.payee.key() == payee, ErrorCode::InvalidPayee);
+    let min_deposit = min_deposit.checked_mul(quantity).ok_or(ErrorCode::MathOverflow)?;
     require!(deposit >= min_deposit, ErrorCode::DepositBelowMinimum);
 
+    // Reference-priced equipment is quoted in its own currency, and the total
+    // and deposit were checked against the listed price and minimum deposit
+    // above before conversion, so a quote cannot undercut the listing through
+    // the rate. Everything from here on is in settlement units at a fixed rate.
+    let fx_rate = settlement_rate(
+        equipment.reference_currency,
+        &ctx.accounts.usdc_mint.key(),
+        ctx.accounts.currency_feed.as_deref().map(|feed| &**feed),
+        ctx.accounts.price_feed.as_ref().map(|feed| feed.as_ref()),
+        now,
+    )?;
+    let (total_amount, deposit, insurance_premium) = match &fx_rate {
+        Some(rate) => {
+            let decimals = ctx.accounts.usdc_mint.decimals;
+            (
+                rate.to_settlement(total_amount, decimals)?,
+                rate.to_settlement(deposit, decimals)?,
+                insurance_premium.map(|premium| rate.to_settlement(premium, decimals)).transpose()?,
+            )
+        }
+        None => (total_amount, deposit, insurance_premium),
+    };
+    require!(total_amount > deposit, ErrorCode::InvalidAmount);
+
+    let fee = marketplace_fee(
+        &ctx.accounts.vendor,
+        ctx.accounts.marketplace.as_deref(),
+        ctx.accounts.treasury_token_account.as_deref(),
+        &ctx.accounts.usdc_mint.key(),
+        FeeKind::Deposit,
+        deposit,
+    )?;
+    let escrowed_deposit = deposit - fee;
+
+    msg!("Transferring deposit to escrow: {}", escrowed_deposit);
+    let received_deposit = transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.buyer_token_account.to_account_info(),
+        &mut ctx.accounts.escrow_token_account,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.buyer.to_account_info(),
+        &[],
+        escrowed_deposit,
+    )?;
+    let mut received_fee = 0;
+    if fee > 0 {
+        let treasury = ctx.accounts.treasury_token_account.as_mut().ok_or(ErrorCode::InvalidTreasury)?;
+        msg!("Transferring deposit fee to treasury: {}", fee);
+        received_fee = transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.buyer_token_account.to_account_info(),
+            treasury,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.buyer.to_account_info(),
+            &[],
+            fee,
+        )?;
+    }
+
     let escrow = &mut ctx.accounts.escrow;
     escrow.equipment = equipment.key();
-    escrow.funder = ctx.accounts.buyer.key();
+    escrow.funder = buyer_key;
     escrow.vendor = equipment.vendor;
-    escrow.amount = deposit;
+    escrow.amount = received_deposit;
     escrow.is_released = false;
-
-    msg!("Transferring deposit to escrow: {}", deposit);
-    anchor_spl::token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.buyer_token_account.to_account_info(),
-                to: ctx.accounts.escrow_token_account.to_account_info(),
-                authority: ctx.accounts.buyer.to_account_info(),
-            },
-        ),
-        deposit,
-    )?;
     msg!("Transfer complete");
 
     msg!("Setting contract state");
-    contract.borrower = ctx.accounts.buyer.key();
+    contract.borrower = buyer_key;
     contract.payee = payee;
     contract.equipment = equipment.key();
-    contract.equipment_unit_index = equipment.sold_quantity + equipment.funded_sold_quantity - 1;
+    contract.equipment_unit_index = equipment.issue_units(quantity)?;
     contract.total_amount = total_amount;
-    contract.amount_paid = deposit;
+    contract.amount_paid = received_deposit + received_fee;
     contract.deposit = deposit;
-    contract.start_date = Clock::get()?.unix_timestamp;
+    contract.start_date = now;
     contract.end_date = contract.start_date + duration;
     contract.contract_unique_id = contract_unique_id;
     contract.last_payment_date = contract.start_date;
     let installment_count = (duration / frequency_seconds) as u64;
     require!(installment_count <= u8::MAX as u64, ErrorCode::TooManyInstallments);
+    if let Some(vendor_marketplace) = ctx.accounts.vendor.marketplace {
+        let marketplace = ctx
+            .accounts
+            .marketplace
+            .as_ref()
+            .filter(|m| m.key() == vendor_marketplace)
+            .ok_or(ErrorCode::MarketplaceRequired)?;
+        marketplace
+            .rules
+            .validate_contract(total_amount, deposit, duration, installment_count)?;
+    }
     contract.installment_count = installment_count as u8;
     contract.paid_installments = 1;
     contract.installment_frequency = installment_frequency;
@@ -165,13 +314,23 @@ pub fn create_contract(
     contract.credit_score_delta = 0;
     contract.stablecoin_mint = ctx.accounts.usdc_mint.key();
     contract.escrow = ctx.accounts.escrow.key();
+    contract.funder_escrow = funder_escrow;
+    contract.fx_rate = fx_rate;
+    contract.unit_price = unit_price;
+    contract.terms_version = terms_version;
+    contract.variant = variant;
+    contract.kind = ContractKind::Bnpl;
+    contract.delivery_status = DeliveryStatus::Pending;
+    contract.unit_asset = None;
+    contract.quantity = quantity;
+    contract.delivered_quantity = 0;
 
     equipment.delivery_status = DeliveryStatus::Pending;
-    if equipment.sold_quantity == vendor_quantity && equipment.funded_quantity > equipment.funded_sold_quantity {
-        equipment.status = EquipmentStatus::PartiallySold;
-    } else if equipment.sold_quantity + equipment.funded_sold_quantity == equipment.total_quantity {
-        equipment.status = EquipmentStatus::Sold;
-    }
+    equipment.active_contracts += 1;
+    equipment.open_escrows += 1;
+    equipment.refresh_status();
+
+    contract.counts_toward_loans = Borrower::open_loan(&ctx.accounts.borrower, ctx.program_id)?;
 
     msg!("Contract creation complete");
     Ok(())
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    errors::ErrorCode,
+    state::{
+        borrower::Borrower,
+        cart::{CartContract, CartLine},
+        config::ProgramConfig,
+        equipment::{DeliveryStatus, Equipment},
+        marketplace::{FeeKind, Marketplace},
+        vendor::Vendor,
+    },
+    utils::{
+        fees::marketplace_fee,
+        transfer::{gross_up_for_transfer_fee, transfer_checked_received},
+    },
+};
+
+/// When the payment completes the cart, remaining accounts must be the
+/// writable `Equipment` accounts of its open lines, each listing once, so
+/// they can be released.
+#[derive(Accounts)]
+pub struct MakeCartPayment<'info> {
+    #[account(mut, has_one = vendor)]
+    pub cart: Box<Account<'info, CartContract>>,
+    pub vendor: Box<Account<'info, Vendor>>,
+    #[account(address = cart.borrower @ ErrorCode::UnauthorizedBuyer)]
+    pub buyer: Signer<'info>,
+    #[account(address = cart.stablecoin_mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = buyer
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = vendor
+    )]
+    pub vendor_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub marketplace: Option<Box<Account<'info, Marketplace>>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// CHECK: The borrower's profile PDA; its loan count is released in logic
+    #[account(
+        mut,
+        seeds = [Borrower::SEED_PREFIX, cart.borrower.as_ref()],
+        bump
+    )]
+    pub borrower_profile: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_PAYMENTS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn make_cart_payment<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MakeCartPayment<'info>>,
+    payment_amount: u64,
+) -> Result<()> {
+    require!(!ctx.accounts.cart.is_completed, ErrorCode::ContractAlreadyCompleted);
+    // Like `make_payment`, the final payment may be grossed up for the mint's
+    // transfer fee since only what arrives is credited.
+    let remaining_amount = ctx.accounts.cart.total_amount - ctx.accounts.cart.amount_paid;
+    require!(
+        payment_amount <= gross_up_for_transfer_fee(&ctx.accounts.usdc_mint, remaining_amount)?,
+        ErrorCode::Overpayment
+    );
+
+    let fee = marketplace_fee(
+        &ctx.accounts.vendor,
+        ctx.accounts.marketplace.as_deref(),
+        ctx.accounts.treasury_token_account.as_deref(),
+        &ctx.accounts.usdc_mint.key(),
+        FeeKind::Installment,
+        payment_amount,
+    )?;
+    let received_vendor = transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.buyer_token_account.to_account_info(),
+        &mut ctx.accounts.vendor_token_account,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.buyer.to_account_info(),
+        &[],
+        payment_amount - fee,
+    )?;
+    let mut received_fee = 0;
+    if fee > 0 {
+        let treasury = ctx.accounts.treasury_token_account.as_mut().ok_or(ErrorCode::InvalidTreasury)?;
+        received_fee = transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.buyer_token_account.to_account_info(),
+            treasury,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.buyer.to_account_info(),
+            &[],
+            fee,
+        )?;
+    }
+
+    let cart = &mut ctx.accounts.cart;
+    cart.amount_paid += received_vendor + received_fee;
+    cart.paid_installments = cart.paid_installments.saturating_add(1);
+    cart.last_payment_date = Clock::get()?.unix_timestamp;
+
+    if cart.amount_paid >= cart.total_amount {
+        cart.is_completed = true;
+        Borrower::close_loan(&ctx.accounts.borrower_profile, ctx.program_id, &mut cart.counts_toward_loans)?;
+        // Cancelled lines already released their listing's contract count.
+        let open_lines: Vec<&CartLine> = cart
+            .lines
+            .iter()
+            .filter(|l| l.delivery_status != DeliveryStatus::Cancelled)
+            .collect();
+        let mut released: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+        for account_info in ctx.remaining_accounts.iter() {
+            require!(!released.contains(account_info.key), ErrorCode::InvalidEquipment);
+            let mut equipment = Account::<Equipment>::try_from(account_info)?;
+            let line_count = open_lines.iter().filter(|l| l.equipment == equipment.key()).count() as u64;
+            require!(line_count > 0, ErrorCode::InvalidEquipment);
+            equipment.active_contracts = equipment.active_contracts.saturating_sub(line_count);
+            equipment.exit(ctx.program_id)?;
+            released.push(equipment.key());
+        }
+        require!(
+            open_lines.iter().all(|l| released.contains(&l.equipment)),
+            ErrorCode::InvalidEquipment
+        );
+        msg!("Cart contract completed");
+    }
+
+    Ok(())
+}
@@ -1,30 +1,75 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount, Transfer},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use crate::{
-    state::{contract::BNPLContract, equipment::Equipment, vendor::Vendor},
+    state::{
+        borrower::Borrower,
+        config::ProgramConfig,
+        contract::BNPLContract,
+        credit_score::CreditScore,
+        equipment::Equipment,
+        marketplace::{FeeKind, Marketplace},
+        vendor::Vendor,
+    },
     errors::ErrorCode,
+    utils::{
+        fees::marketplace_fee,
+        transfer::{gross_up_for_transfer_fee, transfer_checked_received},
+    },
 };
 
 #[derive(Accounts)]
 pub struct MakePayment<'info> {
-    #[account(mut)]
+    #[account(mut, has_one = equipment)]
     pub contract: Account<'info, BNPLContract>,
     #[account(mut)]
     pub equipment: Account<'info, Equipment>,
     #[account(mut)]
     pub buyer: Signer<'info>,
-    pub usdc_mint: Account<'info, Mint>,
-    #[account(mut)]
-    pub buyer_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub payee_token_account: Account<'info, TokenAccount>,
+    #[account(address = contract.stablecoin_mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = buyer
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        constraint = payee_token_account.owner == contract.payee @ ErrorCode::InvalidPayee
+    )]
+    pub payee_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(constraint = vendor.key() == equipment.vendor @ ErrorCode::InvalidVendor)]
     pub vendor: Account<'info, Vendor>,
-    pub token_program: Program<'info, Token>,
+    pub marketplace: Option<Account<'info, Marketplace>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: The borrower's profile PDA; its loan count is released in logic
+    #[account(
+        mut,
+        seeds = [Borrower::SEED_PREFIX, contract.borrower.as_ref()],
+        bump
+    )]
+    pub borrower_profile: UncheckedAccount<'info>,
+    /// Required once the borrower profile exists; scored from this payment.
+    #[account(
+        mut,
+        seeds = [CreditScore::SEED_PREFIX, borrower_profile.key().as_ref()],
+        bump = credit_score.bump
+    )]
+    pub credit_score: Option<Account<'info, CreditScore>>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_PAYMENTS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
 }
 
 pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()> {
@@ -40,28 +85,76 @@ pub fn make_payment(ctx: Context<MakePayment>, payment_amount: u64) -> Result<()
         ErrorCode::InvalidPayee
     );
 
+    let now = Clock::get()?.unix_timestamp;
+    let on_time = !contract.status(now)?.is_payment_overdue;
+    if Borrower::load(&ctx.accounts.borrower_profile, ctx.program_id)?.is_some() {
+        require!(ctx.accounts.credit_score.is_some(), ErrorCode::CreditScoreRequired);
+    }
+
+    // `amount_paid` is credited with what arrives, so on a transfer-fee mint
+    // the final payment has to be grossed up to cover the withheld fee.
     let remaining_amount = contract.total_amount - contract.amount_paid;
-    require!(payment_amount <= remaining_amount, ErrorCode::Overpayment);
+    require!(
+        payment_amount <= gross_up_for_transfer_fee(&ctx.accounts.usdc_mint, remaining_amount)?,
+        ErrorCode::Overpayment
+    );
 
-    anchor_spl::token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.buyer_token_account.to_account_info(),
-                to: ctx.accounts.payee_token_account.to_account_info(),
-                authority: ctx.accounts.buyer.to_account_info(),
-            },
-        ),
+    let fee = marketplace_fee(
+        &ctx.accounts.vendor,
+        ctx.accounts.marketplace.as_ref(),
+        ctx.accounts.treasury_token_account.as_ref(),
+        &ctx.accounts.usdc_mint.key(),
+        FeeKind::Installment,
         payment_amount,
     )?;
+    let payee_amount = payment_amount - fee;
 
-    contract.amount_paid += payment_amount;
+    let received_payee = transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.buyer_token_account.to_account_info(),
+        &mut ctx.accounts.payee_token_account,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.buyer.to_account_info(),
+        &[],
+        payee_amount,
+    )?;
+    let mut received_fee = 0;
+    if fee > 0 {
+        let treasury = ctx.accounts.treasury_token_account.as_mut().ok_or(ErrorCode::InvalidTreasury)?;
+        received_fee = transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.buyer_token_account.to_account_info(),
+            treasury,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.buyer.to_account_info(),
+            &[],
+            fee,
+        )?;
+    }
+
+    if let Some(funder_escrow) = contract.funder_escrow {
+        if let Some(funder_info) = equipment.funders.iter_mut().find(|f| f.escrow == funder_escrow) {
+            funder_info.amount_collected += received_payee;
+        }
+    }
+
+    contract.amount_paid += received_payee + received_fee;
     contract.paid_installments += 1;
-    contract.last_payment_date = Clock::get()?.unix_timestamp;
+    contract.last_payment_date = now;
+
+    // The score moves with what was actually paid, one point per whole token,
+    // up for a payment made by its due date and down for a late one.
+    if let Some(credit_score) = ctx.accounts.credit_score.as_mut() {
+        let points = (received_payee + received_fee) / 10u64.pow(ctx.accounts.usdc_mint.decimals as u32);
+        credit_score.update_score(on_time, points.min(u32::MAX as u64) as u32);
+        contract.credit_score_delta = if on_time { 10 } else { -10 };
+    }
 
     if contract.amount_paid >= contract.total_amount {
         contract.is_completed = true;
+        equipment.active_contracts = equipment.active_contracts.saturating_sub(1);
         contract.credit_score_delta += 10;
+        Borrower::close_loan(&ctx.accounts.borrower_profile, ctx.program_id, &mut contract.counts_toward_loans)?;
     }
 
     Ok(())
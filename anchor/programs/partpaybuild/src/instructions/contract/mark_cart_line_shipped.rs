@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::{
+        cart::CartContract,
+        config::ProgramConfig,
+        delegate::VendorDelegate,
+        equipment::DeliveryStatus,
+        vendor::Vendor,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(line_index: u8)]
+pub struct MarkCartLineShipped<'info> {
+    #[account(
+        mut,
+        has_one = vendor,
+        constraint = (line_index as usize) < cart.lines.len() @ ErrorCode::InvalidCartLines
+    )]
+    pub cart: Box<Account<'info, CartContract>>,
+    pub vendor: Box<Account<'info, Vendor>>,
+    #[account(
+        constraint = vendor.authorizes(
+            &vendor.key(),
+            &authority.key(),
+            vendor_delegate.as_deref(),
+            VendorDelegate::SHIPPING
+        ) @ ErrorCode::MissingVendorPermission
+    )]
+    pub authority: Signer<'info>,
+    pub vendor_delegate: Option<Account<'info, VendorDelegate>>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn mark_cart_line_shipped(ctx: Context<MarkCartLineShipped>, line_index: u8) -> Result<()> {
+    let line = &mut ctx.accounts.cart.lines[line_index as usize];
+    require!(line.delivery_status == DeliveryStatus::Pending, ErrorCode::InvalidDeliveryStatus);
+    line.delivery_status = DeliveryStatus::Shipped;
+    msg!("Cart line {} marked shipped by {}", line_index, ctx.accounts.authority.key());
+    Ok(())
+}
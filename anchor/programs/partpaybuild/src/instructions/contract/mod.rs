@@ -1,7 +1,17 @@
 mod create_contract;
 mod make_payment;
 mod get_contract_status;
+mod create_cart_contract;
+mod make_cart_payment;
+mod confirm_cart_delivery;
+mod mark_cart_line_shipped;
+mod cancel_cart_line;
 
 pub use create_contract::*;
 pub use make_payment::*;
-pub use get_contract_status::*;
\ No newline at end of file
+pub use get_contract_status::*;
+pub use create_cart_contract::*;
+pub use make_cart_payment::*;
+pub use confirm_cart_delivery::*;
+pub use mark_cart_line_shipped::*;
+pub use cancel_cart_line::*;
\ No newline at end of file
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::{
+        config::ProgramConfig,
+        delegate::VendorDelegate,
+        equipment::Equipment,
+        vendor::Vendor,
+    },
+};
+
+#[derive(Accounts)]
+pub struct AdjustStock<'info> {
+    #[account(mut, has_one = vendor)]
+    pub equipment: Account<'info, Equipment>,
+    #[account(mut)]
+    pub vendor: Account<'info, Vendor>,
+    #[account(
+        constraint = vendor.authorizes(
+            &vendor.key(),
+            &authority.key(),
+            vendor_delegate.as_deref(),
+            VendorDelegate::CATALOG
+        ) @ ErrorCode::MissingVendorPermission
+    )]
+    pub authority: Signer<'info>,
+    pub vendor_delegate: Option<Account<'info, VendorDelegate>>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn restock_equipment(ctx: Context<AdjustStock>, variant: u8, quantity: u64) -> Result<()> {
+    require!(quantity > 0, ErrorCode::InvalidAmount);
+    let equipment = &mut ctx.accounts.equipment;
+    let sku = equipment.variant_mut(variant)?;
+    sku.total_quantity = sku
+        .total_quantity
+        .checked_add(quantity)
+        .ok_or(ErrorCode::MathOverflow)?;
+    equipment.total_quantity = equipment
+        .total_quantity
+        .checked_add(quantity)
+        .ok_or(ErrorCode::MathOverflow)?;
+    equipment.refresh_status();
+
+    let vendor = &mut ctx.accounts.vendor;
+    vendor.unit_count = vendor.unit_count.checked_add(quantity).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Restocked {} units, total {}", quantity, equipment.total_quantity);
+    Ok(())
+}
+
+/// Removes unsold vendor stock. Units already sold or committed to funders
+/// stay on the listing.
+pub fn reduce_stock(ctx: Context<AdjustStock>, variant: u8, quantity: u64) -> Result<()> {
+    require!(quantity > 0, ErrorCode::InvalidAmount);
+    let equipment = &mut ctx.accounts.equipment;
+    let sku = equipment.variant_mut(variant)?;
+    let committed = sku.sold_quantity + sku.funded_quantity;
+    let new_total = sku
+        .total_quantity
+        .checked_sub(quantity)
+        .ok_or(ErrorCode::StockBelowCommitted)?;
+    require!(new_total >= committed, ErrorCode::StockBelowCommitted);
+    sku.total_quantity = new_total;
+    // The listing as a whole keeps at least one unit.
+    require!(equipment.total_quantity > quantity, ErrorCode::StockBelowCommitted);
+    equipment.total_quantity -= quantity;
+    equipment.refresh_status();
+
+    let vendor = &mut ctx.accounts.vendor;
+    vendor.unit_count = vendor.unit_count.saturating_sub(quantity);
+
+    msg!("Reduced stock by {} units, total {}", quantity, equipment.total_quantity);
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    errors::ErrorCode,
+    state::{
+        borrower::Borrower,
+        config::ProgramConfig,
+        contract::{BNPLContract, ContractKind},
+        delegate::VendorDelegate,
+        equipment::{DeliveryStatus, Equipment},
+        escrow::Escrow,
+        vendor::Vendor,
+    },
+    utils::{orders::unwind_order, transfer::transfer_checked_received},
+};
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(mut, has_one = equipment, has_one = escrow)]
+    pub contract: Box<Account<'info, BNPLContract>>,
+    #[account(mut, has_one = vendor)]
+    pub equipment: Box<Account<'info, Equipment>>,
+    pub vendor: Box<Account<'info, Vendor>>,
+    #[account(
+        mut,
+        seeds = [
+            b"escrow",
+            equipment.key().as_ref(),
+            contract.borrower.as_ref(),
+            contract.contract_unique_id.as_ref()
+        ],
+        bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = contract.borrower
+    )]
+    pub borrower_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: The borrower's profile PDA; its loan count is released in logic
+    #[account(
+        mut,
+        seeds = [Borrower::SEED_PREFIX, contract.borrower.as_ref()],
+        bump
+    )]
+    pub borrower_profile: UncheckedAccount<'info>,
+    /// The borrower before shipment, or the vendor with shipping permission
+    /// until delivery.
+    pub authority: Signer<'info>,
+    pub vendor_delegate: Option<Account<'info, VendorDelegate>>,
+    #[account(address = contract.stablecoin_mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Cancels an undelivered order, refunds its escrow to the borrower and puts
+/// its units back on sale. BNPL orders can only be cancelled before any
+/// installment beyond the deposit.
+pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+    let contract = &ctx.accounts.contract;
+    let authority = ctx.accounts.authority.key();
+    let is_vendor = ctx.accounts.vendor.authorizes(
+        &ctx.accounts.vendor.key(),
+        &authority,
+        ctx.accounts.vendor_delegate.as_deref(),
+        VendorDelegate::SHIPPING,
+    );
+    let cancellable = match contract.delivery_status {
+        DeliveryStatus::Pending => is_vendor || authority == contract.borrower,
+        DeliveryStatus::Shipped => is_vendor,
+        _ => false,
+    };
+    require!(cancellable && contract.delivered_quantity == 0, ErrorCode::OrderNotCancellable);
+    if contract.kind == ContractKind::Bnpl {
+        require!(contract.paid_installments <= 1, ErrorCode::OrderHasPayments);
+    }
+    require!(!ctx.accounts.escrow.is_released, ErrorCode::FundsAlreadyReleased);
+
+    let equipment_key = ctx.accounts.equipment.key();
+    let borrower = contract.borrower;
+    let unique_id = contract.contract_unique_id;
+    transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.escrow_token_account.to_account_info(),
+        &mut ctx.accounts.borrower_token_account,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.escrow.to_account_info(),
+        &[&[
+            b"escrow",
+            equipment_key.as_ref(),
+            borrower.as_ref(),
+            unique_id.as_ref(),
+            &[ctx.bumps.escrow],
+        ]],
+        ctx.accounts.escrow.amount,
+    )?;
+    ctx.accounts.escrow.is_released = true;
+
+    Borrower::close_loan(
+        &ctx.accounts.borrower_profile,
+        ctx.program_id,
+        &mut ctx.accounts.contract.counts_toward_loans,
+    )?;
+    unwind_order(&mut ctx.accounts.equipment, &mut ctx.accounts.contract)?;
+    msg!("Order cancelled: {}", ctx.accounts.contract.key());
+    Ok(())
+}
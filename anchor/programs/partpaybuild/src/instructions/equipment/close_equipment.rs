@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use mpl_core::{instructions::BurnV1Builder, ID as MPL_CORE_ID};
+use crate::{
+    errors::ErrorCode,
+    state::{
+        catalog::VendorCatalogPage,
+        category::{CategoryIndexPage, EquipmentCategory},
+        config::ProgramConfig,
+        equipment::Equipment,
+        vendor::Vendor,
+    },
+};
+
+#[derive(Accounts)]
+pub struct CloseEquipment<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = vendor,
+        constraint = equipment.delisted @ ErrorCode::EquipmentNotSettled,
+        constraint = equipment.is_settled() @ ErrorCode::EquipmentNotSettled
+    )]
+    pub equipment: Box<Account<'info, Equipment>>,
+    /// CHECK: Checked against `equipment.asset`, burned by Metaplex Core
+    #[account(mut, address = equipment.asset @ ErrorCode::InvalidEquipment)]
+    pub equipment_asset: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::InvalidVendorAuthority,
+        has_one = collection @ ErrorCode::InvalidVendor
+    )]
+    pub vendor: Box<Account<'info, Vendor>>,
+    #[account(
+        mut,
+        seeds = [
+            VendorCatalogPage::SEED_PREFIX,
+            vendor.key().as_ref(),
+            &VendorCatalogPage::page_for_slot(equipment.catalog_slot).to_le_bytes()
+        ],
+        bump = catalog_page.bump
+    )]
+    pub catalog_page: Box<Account<'info, VendorCatalogPage>>,
+    /// Required when the listing is in a category.
+    #[account(mut)]
+    pub category: Option<Box<Account<'info, EquipmentCategory>>>,
+    #[account(mut)]
+    pub category_page: Option<Box<Account<'info, CategoryIndexPage>>>,
+    /// CHECK: Checked against `vendor.collection`, updated by Metaplex Core
+    #[account(mut)]
+    pub collection: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This is the Metaplex Core program
+    #[account(address = MPL_CORE_ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Burns the listing asset, clears its catalog slot and returns the rent to
+/// the vendor authority.
+pub fn close_equipment(ctx: Context<CloseEquipment>) -> Result<()> {
+    let vendor = &ctx.accounts.vendor;
+    let burn_ix = BurnV1Builder::new()
+        .asset(ctx.accounts.equipment_asset.key())
+        .collection(Some(ctx.accounts.collection.key()))
+        .payer(ctx.accounts.authority.key())
+        .authority(Some(vendor.key()))
+        .system_program(Some(ctx.accounts.system_program.key()))
+        .instruction();
+
+    invoke_signed(
+        &burn_ix,
+        &[
+            ctx.accounts.equipment_asset.to_account_info(),
+            ctx.accounts.collection.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            vendor.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.mpl_core_program.to_account_info(),
+        ],
+        &[&vendor.signer_seeds()],
+    )
+    .map_err(|e| {
+        msg!("Error invoking Metaplex Core program: {:?}", e);
+        error!(ErrorCode::MetaplexError)
+    })?;
+
+    let equipment = &ctx.accounts.equipment;
+    let catalog_page = &mut ctx.accounts.catalog_page;
+    let offset = (equipment.catalog_slot - catalog_page.first_slot()) as usize;
+    if let Some(entry) = catalog_page.entries.get_mut(offset) {
+        if *entry == equipment.key() {
+            *entry = Pubkey::default();
+        }
+    }
+
+    if let Some(category_key) = equipment.category {
+        let category = ctx
+            .accounts
+            .category
+            .as_mut()
+            .filter(|c| c.key() == category_key)
+            .ok_or(ErrorCode::InvalidCategory)?;
+        let category_page = ctx
+            .accounts
+            .category_page
+            .as_mut()
+            .filter(|p| {
+                p.category == category_key
+                    && p.page == CategoryIndexPage::page_for_slot(equipment.category_slot)
+            })
+            .ok_or(ErrorCode::InvalidCategory)?;
+        let offset = (equipment.category_slot - category_page.first_slot()) as usize;
+        if let Some(entry) = category_page.entries.get_mut(offset) {
+            if *entry == equipment.key() {
+                *entry = Pubkey::default();
+            }
+        }
+        category.listing_count = category.listing_count.saturating_sub(1);
+    }
+
+    let total_quantity = equipment.total_quantity;
+    let vendor = &mut ctx.accounts.vendor;
+    vendor.listing_count = vendor.listing_count.saturating_sub(1);
+    vendor.unit_count = vendor.unit_count.saturating_sub(total_quantity);
+
+    msg!("Equipment closed: {}", ctx.accounts.equipment.key());
+    Ok(())
+}
@@ -1,13 +1,19 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, Mint};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use mpl_core::ID as MPL_CORE_ID;
 use crate::{
     state::{
+        config::ProgramConfig,
         equipment::{Equipment, DeliveryStatus},
         escrow::Escrow,
         contract::BNPLContract,
     },
     errors::ErrorCode,
-    constants::CONTRACT_SEED
+    constants::CONTRACT_SEED,
+    utils::{
+        orders::{complete_delivery, escrow_share, mint_unit_asset, unit_asset_accounts},
+        transfer::transfer_checked_received,
+    },
 };
 
 
@@ -26,9 +32,10 @@ pub struct ConfirmDelivery<'info> {
     #[account(
         mut,
         associated_token::mint = usdc_mint,
-        associated_token::authority = escrow
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
     )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
         seeds = [CONTRACT_SEED, borrower.key().as_ref(), equipment.key().as_ref(), unique_id.as_ref()],
@@ -40,45 +47,107 @@ pub struct ConfirmDelivery<'info> {
     pub contract: Account<'info, BNPLContract>,
     #[account(mut)]
     pub borrower: Signer<'info>,
-    #[account(mut)]
-    pub payee_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = payee
+    )]
+    pub payee_token_account: InterfaceAccount<'info, TokenAccount>,
     /// CHECK: Validated via contract.payee
     pub payee: AccountInfo<'info>,
-    /// CHECK: Used only for equipment validation
+    /// CHECK: Used for equipment validation and as the unit asset's update authority
     pub vendor: AccountInfo<'info>,
-    pub usdc_mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
+    /// CHECK: PDA for the first unit being delivered, checked in logic and
+    /// initialized by Metaplex Core. Further units' assets follow in the
+    /// remaining accounts.
+    #[account(mut)]
+    pub unit_asset: UncheckedAccount<'info>,
+    #[account(address = contract.stablecoin_mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This is the Metaplex Core program
+    #[account(address = MPL_CORE_ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
 }
 
-pub fn confirm_delivery(ctx: Context<ConfirmDelivery>, unique_id: Pubkey) -> Result<()> {
+/// Confirms delivery of `units` of an order: releases their share of the
+/// escrow to the payee and mints one asset per unit to the borrower. Serves
+/// BNPL and outright orders alike.
+pub fn confirm_delivery<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConfirmDelivery<'info>>,
+    unique_id: Pubkey,
+    units: u64,
+) -> Result<()> {
     let equipment = &mut ctx.accounts.equipment;
     let escrow = &mut ctx.accounts.escrow;
-    let contract = &ctx.accounts.contract;
+    let contract = &mut ctx.accounts.contract;
 
     require!(contract.borrower == ctx.accounts.borrower.key(), ErrorCode::Unauthorized);
-    require!(equipment.delivery_status == DeliveryStatus::Pending, ErrorCode::InvalidDeliveryStatus);
+    require!(
+        matches!(contract.delivery_status, DeliveryStatus::Pending | DeliveryStatus::Shipped),
+        ErrorCode::InvalidDeliveryStatus
+    );
     require!(!escrow.is_released, ErrorCode::FundsAlreadyReleased);
     require!(contract.payee == ctx.accounts.payee.key(), ErrorCode::InvalidPayee);
+    require!(
+        units > 0 && units <= contract.undelivered_quantity(),
+        ErrorCode::InvalidUnitCount
+    );
 
-    anchor_spl::token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow_token_account.to_account_info(),
-                to: ctx.accounts.payee_token_account.to_account_info(),
-                authority: escrow.to_account_info(),
-            },
-        ).with_signer(&[&[
+    let contract_key = contract.key();
+    let unit_assets = unit_asset_accounts(
+        &ctx.accounts.unit_asset.to_account_info(),
+        ctx.remaining_accounts,
+        ctx.program_id,
+        &contract_key,
+        contract.delivered_quantity,
+        units,
+    )?;
+    let release_amount = escrow_share(escrow.amount, contract, units);
+
+    let equipment_key = equipment.key();
+    let borrower_key = ctx.accounts.borrower.key();
+    let received = transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.escrow_token_account.to_account_info(),
+        &mut ctx.accounts.payee_token_account,
+        &ctx.accounts.usdc_mint,
+        escrow.to_account_info(),
+        &[&[
             b"escrow",
-            equipment.key().as_ref(),
-            ctx.accounts.borrower.key().as_ref(),
+            equipment_key.as_ref(),
+            borrower_key.as_ref(),
             unique_id.as_ref(),
             &[ctx.bumps.escrow]
-        ]]),
-        escrow.amount,
+        ]],
+        release_amount,
     )?;
 
-    equipment.delivery_status = DeliveryStatus::Delivered;
-    escrow.is_released = true;
+    escrow.amount -= release_amount;
+    escrow.is_released = units == contract.undelivered_quantity();
+
+    for (i, (unit_asset, bump)) in unit_assets.iter().enumerate() {
+        mint_unit_asset(
+            unit_asset,
+            *bump,
+            &contract_key,
+            contract.delivered_quantity + i as u64,
+            equipment,
+            &ctx.accounts.borrower.to_account_info(),
+            &ctx.accounts.borrower.to_account_info(),
+            &ctx.accounts.vendor,
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.mpl_core_program.to_account_info(),
+        )?;
+    }
+    complete_delivery(equipment, contract, unit_assets[0].0.key(), received, units);
+    msg!("Delivered {} of {} units", contract.delivered_quantity, contract.quantity);
     Ok(())
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, Mint};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::{
-    state::{equipment::{Equipment, DeliveryStatus}, escrow::Escrow},
+    state::{config::ProgramConfig, equipment::{Equipment, DeliveryStatus}, escrow::Escrow},
     errors::ErrorCode,
+    utils::transfer::transfer_checked_received,
 };
 
 #[derive(Accounts)]
@@ -20,19 +21,31 @@ pub struct ConfirmFundedDelivery<'info> {
     #[account(
         mut,
         associated_token::mint = usdc_mint,
-        associated_token::authority = escrow
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
     )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
     pub confirmer: Signer<'info>,
-    #[account(mut)]
-    pub payee_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = payee
+    )]
+    pub payee_token_account: InterfaceAccount<'info, TokenAccount>,
     /// CHECK: Validated via logic
     pub payee: AccountInfo<'info>,
     /// CHECK: Used for equipment validation
     pub vendor: AccountInfo<'info>,
-    pub usdc_mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
+    #[account(address = equipment.mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
 }
 
 pub fn confirm_funded_delivery(ctx: Context<ConfirmFundedDelivery>, unique_id: Pubkey) -> Result<()> {
@@ -59,30 +72,34 @@ pub fn confirm_funded_delivery(ctx: Context<ConfirmFundedDelivery>, unique_id: P
         ctx.accounts.confirmer.key() == ctx.accounts.escrow.funder || funder_info.borrower == Some(ctx.accounts.confirmer.key()),
         ErrorCode::Unauthorized
     );
-    require!(equipment.delivery_status == DeliveryStatus::Pending, ErrorCode::InvalidDeliveryStatus);
+    require!(
+        matches!(equipment.delivery_status, DeliveryStatus::Pending | DeliveryStatus::Shipped),
+        ErrorCode::InvalidDeliveryStatus
+    );
     require!(!ctx.accounts.escrow.is_released, ErrorCode::FundsAlreadyReleased);
     require!(ctx.accounts.payee.key() == expected_payee, ErrorCode::InvalidPayee);
 
-    anchor_spl::token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow_token_account.to_account_info(),
-                to: ctx.accounts.payee_token_account.to_account_info(),
-                authority: ctx.accounts.escrow.to_account_info(),
-            },
-        ).with_signer(&[&[
+    let equipment_key = equipment.key();
+    let confirmer_key = ctx.accounts.confirmer.key();
+    transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.escrow_token_account.to_account_info(),
+        &mut ctx.accounts.payee_token_account,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.escrow.to_account_info(),
+        &[&[
             b"escrow",
-            equipment.key().as_ref(),
-            ctx.accounts.confirmer.key().as_ref(),
+            equipment_key.as_ref(),
+            confirmer_key.as_ref(),
             unique_id.as_ref(),
             &[ctx.bumps.escrow]
-        ]]),
+        ]],
         ctx.accounts.escrow.amount,
     )?;
 
     equipment.delivery_status = DeliveryStatus::Delivered;
     ctx.accounts.escrow.is_released = true;
+    equipment.open_escrows = equipment.open_escrows.saturating_sub(1);
 
     Ok(())
 }
\ No newline at end of file
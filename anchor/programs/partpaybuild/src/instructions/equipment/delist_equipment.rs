@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::{config::ProgramConfig, delegate::VendorDelegate, equipment::Equipment, vendor::Vendor},
+};
+
+#[derive(Accounts)]
+pub struct DelistEquipment<'info> {
+    #[account(mut, has_one = vendor)]
+    pub equipment: Account<'info, Equipment>,
+    pub vendor: Account<'info, Vendor>,
+    #[account(
+        constraint = vendor.authorizes(
+            &vendor.key(),
+            &authority.key(),
+            vendor_delegate.as_deref(),
+            VendorDelegate::CATALOG
+        ) @ ErrorCode::MissingVendorPermission
+    )]
+    pub authority: Signer<'info>,
+    pub vendor_delegate: Option<Account<'info, VendorDelegate>>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Takes the listing off sale. Existing contracts and funder positions keep
+/// being serviced; the listing can be closed once they have settled.
+pub fn delist_equipment(ctx: Context<DelistEquipment>) -> Result<()> {
+    let equipment = &mut ctx.accounts.equipment;
+    require!(!equipment.delisted, ErrorCode::EquipmentDelisted);
+    equipment.delisted = true;
+    msg!("Equipment delisted: {}", equipment.key());
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::{
+        config::ProgramConfig,
+        delegate::VendorDelegate,
+        equipment::{Equipment, EquipmentVariant},
+        vendor::Vendor,
+    },
+    utils::validation::{validate_name, validate_price},
+};
+
+#[derive(Accounts)]
+pub struct ManageEquipmentVariant<'info> {
+    #[account(mut, has_one = vendor)]
+    pub equipment: Account<'info, Equipment>,
+    #[account(mut)]
+    pub vendor: Account<'info, Vendor>,
+    #[account(
+        constraint = vendor.authorizes(
+            &vendor.key(),
+            &authority.key(),
+            vendor_delegate.as_deref(),
+            VendorDelegate::CATALOG
+        ) @ ErrorCode::MissingVendorPermission
+    )]
+    pub authority: Signer<'info>,
+    pub vendor_delegate: Option<Account<'info, VendorDelegate>>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn add_equipment_variant(
+    ctx: Context<ManageEquipmentVariant>,
+    name: String,
+    price: u64,
+    minimum_deposit: u64,
+    quantity: u64,
+) -> Result<()> {
+    validate_name(&name)?;
+    validate_price(price)?;
+    validate_price(minimum_deposit)?;
+    require!(quantity > 0, ErrorCode::InvalidAmount);
+
+    let equipment = &mut ctx.accounts.equipment;
+    require!(!equipment.delisted, ErrorCode::EquipmentDelisted);
+    require!(
+        equipment.variants.len() < Equipment::MAX_VARIANTS,
+        ErrorCode::TooManyVariants
+    );
+    equipment.variants.push(EquipmentVariant {
+        name,
+        price,
+        minimum_deposit,
+        total_quantity: quantity,
+        funded_quantity: 0,
+        sold_quantity: 0,
+        funded_sold_quantity: 0,
+    });
+    equipment.total_quantity = equipment
+        .total_quantity
+        .checked_add(quantity)
+        .ok_or(ErrorCode::MathOverflow)?;
+    equipment.refresh_status();
+
+    let vendor = &mut ctx.accounts.vendor;
+    vendor.unit_count = vendor.unit_count.checked_add(quantity).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Added variant {} with {} units", equipment.variants.len() - 1, quantity);
+    Ok(())
+}
+
+/// Updates a variant's label and terms. A price change bumps the listing's
+/// terms version like `update_equipment` does; changes to variant 0 are
+/// mirrored onto the listing.
+pub fn update_equipment_variant(
+    ctx: Context<ManageEquipmentVariant>,
+    variant: u8,
+    name: Option<String>,
+    price: Option<u64>,
+    minimum_deposit: Option<u64>,
+) -> Result<()> {
+    let equipment = &mut ctx.accounts.equipment;
+    let mut repriced = false;
+    {
+        let sku = equipment.variant_mut(variant)?;
+        if let Some(new_name) = name {
+            validate_name(&new_name)?;
+            sku.name = new_name;
+        }
+        if let Some(new_price) = price {
+            validate_price(new_price)?;
+            repriced = new_price != sku.price;
+            sku.price = new_price;
+        }
+        if let Some(new_minimum_deposit) = minimum_deposit {
+            validate_price(new_minimum_deposit)?;
+            sku.minimum_deposit = new_minimum_deposit;
+        }
+    }
+
+    if variant == 0 {
+        equipment.price = equipment.variants[0].price;
+        equipment.minimum_deposit = equipment.variants[0].minimum_deposit;
+    }
+    if repriced {
+        equipment.terms_version = equipment
+            .terms_version
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    msg!("Updated variant {}, terms version {}", variant, equipment.terms_version);
+    Ok(())
+}
@@ -1,15 +1,22 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount, Transfer},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use crate::{
     state::{
-        equipment::{Equipment, EquipmentStatus, FunderInfo, DeliveryStatus}, 
+        config::ProgramConfig,
+        equipment::{Equipment, EquipmentStatus, FunderCriteria, FunderInfo, FunderReturn},
+        marketplace::{FeeKind, Marketplace},
         vendor::Vendor,
         escrow::Escrow
     },
     errors::ErrorCode,
+    utils::{
+        fees::marketplace_fee,
+        funding::{record_funding, validate_funding},
+        transfer::transfer_checked_received,
+    },
 };
 
 #[derive(Accounts)]
@@ -27,21 +34,43 @@ pub struct FundEquipmentForListing<'info> {
         init_if_needed,
         payer = funder,
         associated_token::mint = usdc_mint,
-        associated_token::authority = escrow
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
     )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
     pub equipment: Account<'info, Equipment>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vendor.key() == equipment.vendor @ ErrorCode::InvalidVendor,
+        constraint = vendor.is_active() @ ErrorCode::VendorNotActive
+    )]
     pub vendor: Account<'info, Vendor>,
     #[account(mut)]
     pub funder: Signer<'info>,
-    pub usdc_mint: Account<'info, Mint>,
+    #[account(
+        address = equipment.mint @ ErrorCode::InvalidMint,
+        constraint = config.is_mint_allowed(&usdc_mint.key()) @ ErrorCode::MintNotAllowed
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub marketplace: Option<Box<Account<'info, Marketplace>>>,
     #[account(mut)]
-    pub funder_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = funder
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_FUNDING) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
 }
 
 pub fn fund_equipment_for_listing(
@@ -50,47 +79,72 @@ pub fn fund_equipment_for_listing(
     minimum_deposit: u64,
     duration_seconds: i64,
     _unique_id: Pubkey,
+    criteria: FunderCriteria,
+    funder_return: FunderReturn,
+    variant: u8,
 ) -> Result<()> {
-    let equipment = &mut ctx.accounts.equipment;
-    let available_to_fund = equipment.total_quantity - equipment.funded_quantity - equipment.sold_quantity;
-    require!(quantity_to_fund <= available_to_fund, ErrorCode::InsufficientQuantity);
-    require!(
-        equipment.status == EquipmentStatus::Available || 
-        equipment.status == EquipmentStatus::Funded || 
-        equipment.status == EquipmentStatus::Reserved,
-        ErrorCode::EquipmentNotAvailable
-    );
-
-    let total_payment = equipment.price.checked_mul(quantity_to_fund).ok_or(ErrorCode::MathOverflow)?;
-    anchor_spl::token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.funder_token_account.to_account_info(),
-                to: ctx.accounts.escrow_token_account.to_account_info(),
-                authority: ctx.accounts.funder.to_account_info(),
-            },
-        ),
+    let total_payment = validate_funding(&ctx.accounts.equipment, variant, quantity_to_fund)?;
+    let fee = marketplace_fee(
+        &ctx.accounts.vendor,
+        ctx.accounts.marketplace.as_deref(),
+        ctx.accounts.treasury_token_account.as_deref(),
+        &ctx.accounts.usdc_mint.key(),
+        FeeKind::Funding,
         total_payment,
     )?;
+    let escrow_amount = total_payment - fee;
 
-    equipment.funded_quantity += quantity_to_fund;
-    equipment.funders.push(Box::new(FunderInfo {
-        funder: ctx.accounts.funder.key(),
-        quantity: quantity_to_fund,
-        minimum_deposit,
-        duration_seconds,
-        borrower: None,
-        escrow: ctx.accounts.escrow.key(),
-    }));
-    equipment.status = EquipmentStatus::Funded;
-    equipment.delivery_status = DeliveryStatus::Pending;
+    let received_amount = transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.funder_token_account.to_account_info(),
+        &mut ctx.accounts.escrow_token_account,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.funder.to_account_info(),
+        &[],
+        escrow_amount,
+    )?;
+    if fee > 0 {
+        let treasury = ctx.accounts.treasury_token_account.as_mut().ok_or(ErrorCode::InvalidTreasury)?;
+        transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.funder_token_account.to_account_info(),
+            treasury,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.funder.to_account_info(),
+            &[],
+            fee,
+        )?;
+    }
 
-    ctx.accounts.escrow.equipment = ctx.accounts.equipment.key();
-    ctx.accounts.escrow.funder = ctx.accounts.funder.key();
-    ctx.accounts.escrow.vendor = ctx.accounts.vendor.key();
-    ctx.accounts.escrow.amount = total_payment;
-    ctx.accounts.escrow.is_released = false;
+    let equipment_key = ctx.accounts.equipment.key();
+    let escrow_key = ctx.accounts.escrow.key();
+    let unit_price = ctx.accounts.equipment.variant(variant)?.price;
+    let terms_version = ctx.accounts.equipment.terms_version;
+    record_funding(
+        &mut ctx.accounts.equipment,
+        equipment_key,
+        &mut ctx.accounts.escrow,
+        received_amount,
+        FunderInfo {
+            funder: ctx.accounts.funder.key(),
+            quantity: quantity_to_fund,
+            minimum_deposit,
+            duration_seconds,
+            borrower: None,
+            escrow: escrow_key,
+            criteria,
+            funder_return,
+            amount_funded: total_payment,
+            units_sold: 0,
+            sold_principal: 0,
+            sold_repayable: 0,
+            amount_collected: 0,
+            unit_price,
+            terms_version,
+            variant,
+        },
+        EquipmentStatus::Funded,
+    )?;
 
     Ok(())
 }
\ No newline at end of file
@@ -1,51 +1,85 @@
 use anchor_lang::prelude::*;
-use crate::state::vendor::{Vendor, EquipmentInfo, VendorEquipmentResponse};
+use crate::state::{
+    catalog::VendorCatalogPage,
+    vendor::{Vendor, EquipmentInfo, VendorEquipmentResponse},
+};
 use crate::utils::load_equipment::load_equipment_account;
 
+/// Reads one catalog page. Remaining accounts are the equipment accounts for
+/// that page's entries, in order; anything that does not match the index is
+/// reported in `failed_loads`.
 #[derive(Accounts)]
+#[instruction(page: u32)]
 pub struct GetAllVendorEquipment<'info> {
     pub vendor: Account<'info, Vendor>,
+    #[account(
+        seeds = [VendorCatalogPage::SEED_PREFIX, vendor.key().as_ref(), &page.to_le_bytes()],
+        bump = catalog_page.bump
+    )]
+    pub catalog_page: Account<'info, VendorCatalogPage>,
 }
 
 pub fn get_all_vendor_equipment<'info>(
-    ctx: Context<'_, '_, 'info, 'info, GetAllVendorEquipment<'info>>
+    ctx: Context<'_, '_, 'info, 'info, GetAllVendorEquipment<'info>>,
+    _page: u32,
 ) -> Result<VendorEquipmentResponse> {
     let vendor = &ctx.accounts.vendor;
+    let catalog_page = &ctx.accounts.catalog_page;
     let mut response = VendorEquipmentResponse {
-        total_count: vendor.equipment_count,
+        listing_count: vendor.listing_count,
+        unit_count: vendor.unit_count,
         equipment: Vec::new(),
         failed_loads: Vec::new(),
     };
 
-    if vendor.equipment_count == 0 {
-        return Ok(response);
-    }
-
-    for (i, account_info) in ctx.remaining_accounts.iter().enumerate() {
-        if i >= vendor.equipment_count as usize {
-            break;
+    let first_slot = catalog_page.first_slot();
+    for (offset, entry) in catalog_page.entries.iter().enumerate() {
+        let slot = first_slot + offset as u64;
+        // Closed listings leave an empty slot behind.
+        if *entry == Pubkey::default() {
+            continue;
         }
+        let account_info = match ctx.remaining_accounts.get(offset) {
+            Some(account_info) if account_info.key == entry => account_info,
+            _ => {
+                response.failed_loads.push(slot);
+                continue;
+            }
+        };
 
         match load_equipment_account(
             &vendor.key(),
-            i as u64,
+            slot,
             account_info,
             ctx.program_id,
         ) {
             Ok(equipment) => {
+                let available_for_bnpl = equipment.available_for_bnpl();
+                let available_for_funding = equipment.available_for_funding();
+                let (min_price, max_price) = equipment.price_range();
                 response.equipment.push(EquipmentInfo {
                     name: equipment.name,
                     price: equipment.price,
                     uri: equipment.uri,
                     asset: equipment.asset,
-                    index: i as u64,
+                    index: slot,
+                    status: equipment.status,
+                    total_quantity: equipment.total_quantity,
+                    funded_quantity: equipment.funded_quantity,
+                    sold_quantity: equipment.sold_quantity,
+                    funded_sold_quantity: equipment.funded_sold_quantity,
+                    available_for_bnpl,
+                    available_for_funding,
+                    variant_count: equipment.variants.len() as u8,
+                    min_price,
+                    max_price,
                 });
             },
             Err(_) => {
-                response.failed_loads.push(i as u64);
+                response.failed_loads.push(slot);
             }
         }
     }
 
     Ok(response)
-}
\ No newline at end of file
+}
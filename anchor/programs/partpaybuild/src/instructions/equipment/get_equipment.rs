@@ -10,9 +10,11 @@ pub struct GetEquipment<'info> {
 
 pub fn get_equipment(ctx: Context<GetEquipment>) -> Result<Equipment> {
     let equipment = &ctx.accounts.equipment;
+    let now = Clock::get()?.unix_timestamp;
     Ok(Equipment {
         vendor: equipment.vendor,
         asset: equipment.asset,
+        mint: equipment.mint,
         name: equipment.name.clone(),
         uri: equipment.uri.clone(),
         price: equipment.price,
@@ -23,9 +25,21 @@ pub fn get_equipment(ctx: Context<GetEquipment>) -> Result<Equipment> {
         funded_quantity: equipment.funded_quantity,
         unique_id: equipment.unique_id,
         funders: equipment.funders.clone(),
-        payment_preference: equipment.payment_preference.clone(),
+        payment_preference: equipment.effective_payment_preference(now),
         minimum_deposit: equipment.minimum_deposit,
         max_duration_seconds: equipment.max_duration_seconds,
         delivery_status: equipment.delivery_status.clone(),
+        reference_currency: equipment.reference_currency,
+        catalog_slot: equipment.catalog_slot,
+        delisted: equipment.delisted,
+        active_contracts: equipment.active_contracts,
+        open_escrows: equipment.open_escrows,
+        terms_version: equipment.terms_version,
+        variants: equipment.variants.clone(),
+        category: equipment.category,
+        category_slot: equipment.category_slot,
+        tags: equipment.tags.clone(),
+        listed_at: equipment.listed_at,
+        units_issued: equipment.units_issued,
     })
 }
@@ -26,13 +26,20 @@ pub fn get_funded_equipment<'info>(ctx: Context<'_, '_, 'info, 'info, GetFunderE
             equipment_list.push(FunderEquipmentInfo {
                 equipment_pda: equipment.key(),
                 name: equipment.name.clone(),
-                price: equipment.price,
+                price: funder_info.unit_price,
                 uri: equipment.uri.clone(),
                 asset: equipment.asset,
+                variant: funder_info.variant,
                 funded_quantity: funder_info.quantity,
                 total_quantity: equipment.total_quantity,
                 minimum_deposit: funder_info.minimum_deposit,
                 duration_seconds: funder_info.duration_seconds,
+                funder_return: funder_info.funder_return.clone(),
+                amount_funded: funder_info.amount_funded,
+                units_sold: funder_info.units_sold,
+                amount_collected: funder_info.amount_collected,
+                realized_return: funder_info.realized_return(),
+                realized_yield_bps: funder_info.realized_yield_bps(),
             });
         }
     }
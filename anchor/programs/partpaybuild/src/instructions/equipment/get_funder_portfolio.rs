@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{DEFAULT_AFTER_SECONDS, SECONDS_PER_DAY},
+    errors::ErrorCode,
+    state::{
+        contract::BNPLContract,
+        equipment::Equipment,
+        funded::FunderPortfolioResponse,
+    },
+};
+
+#[derive(Accounts)]
+pub struct GetFunderPortfolio<'info> {
+    pub funder: Signer<'info>,
+}
+
+/// Remaining accounts are the funder's `Equipment` positions followed by the
+/// `BNPLContract`s sold from them, in any order.
+pub fn get_funder_portfolio<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetFunderPortfolio<'info>>
+) -> Result<FunderPortfolioResponse> {
+    let funder_key = ctx.accounts.funder.key();
+    let now = Clock::get().map_err(|_| error!(ErrorCode::ClockUnavailable))?.unix_timestamp;
+    let mut response = FunderPortfolioResponse::default();
+
+    let mut position_escrows: Vec<Pubkey> = Vec::new();
+    let mut contracts: Vec<Account<'info, BNPLContract>> = Vec::new();
+    let mut principal_collected: u64 = 0;
+
+    for account_info in ctx.remaining_accounts.iter() {
+        if let Ok(equipment) = Account::<Equipment>::try_from(account_info) {
+            for funder_info in equipment.funders.iter().filter(|f| f.funder == funder_key) {
+                if position_escrows.contains(&funder_info.escrow) {
+                    continue;
+                }
+                position_escrows.push(funder_info.escrow);
+                response.position_count += 1;
+                response.deployed_capital += funder_info.amount_funded;
+                response.repaid_to_date += funder_info.amount_collected;
+                response.outstanding_principal += funder_info.outstanding_principal()?;
+                principal_collected += funder_info.principal_collected()?;
+            }
+        } else if let Ok(contract) = Account::<BNPLContract>::try_from(account_info) {
+            contracts.push(contract);
+        }
+    }
+
+    for contract in contracts.iter() {
+        let belongs_to_funder = contract.payee == funder_key
+            && contract
+                .funder_escrow
+                .is_some_and(|escrow| position_escrows.contains(&escrow));
+        if !belongs_to_funder || contract.is_completed {
+            continue;
+        }
+        response.contract_count += 1;
+
+        let status = contract.status(now)?;
+        if !status.is_payment_overdue {
+            continue;
+        }
+        let overdue_seconds = now - status.next_payment_due;
+        if overdue_seconds > DEFAULT_AFTER_SECONDS {
+            response.defaults += 1;
+        }
+
+        let amount_overdue = contract.amount_overdue(now)?.min(status.remaining_amount);
+        let days_overdue = (overdue_seconds + SECONDS_PER_DAY - 1) / SECONDS_PER_DAY;
+        match days_overdue {
+            0..=30 => response.overdue_1_30_days += amount_overdue,
+            31..=60 => response.overdue_31_60_days += amount_overdue,
+            _ => response.overdue_over_60_days += amount_overdue,
+        }
+    }
+
+    if response.deployed_capital > 0 {
+        // Only the return part of the collections so far, not the principal
+        // still to be repaid.
+        let realized_return = response.repaid_to_date as i128 - principal_collected as i128;
+        response.current_yield_bps =
+            (realized_return * 10_000 / response.deployed_capital as i128) as i64;
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::{
+        config::ProgramConfig,
+        contract::BNPLContract,
+        delegate::VendorDelegate,
+        equipment::{DeliveryStatus, Equipment},
+        vendor::Vendor,
+    },
+};
+
+#[derive(Accounts)]
+pub struct MarkShipped<'info> {
+    #[account(mut, has_one = equipment)]
+    pub contract: Account<'info, BNPLContract>,
+    #[account(has_one = vendor)]
+    pub equipment: Account<'info, Equipment>,
+    pub vendor: Account<'info, Vendor>,
+    #[account(
+        constraint = vendor.authorizes(
+            &vendor.key(),
+            &authority.key(),
+            vendor_delegate.as_deref(),
+            VendorDelegate::SHIPPING
+        ) @ ErrorCode::MissingVendorPermission
+    )]
+    pub authority: Signer<'info>,
+    pub vendor_delegate: Option<Account<'info, VendorDelegate>>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn mark_shipped(ctx: Context<MarkShipped>) -> Result<()> {
+    let contract = &mut ctx.accounts.contract;
+    require!(contract.delivery_status == DeliveryStatus::Pending, ErrorCode::InvalidDeliveryStatus);
+    contract.delivery_status = DeliveryStatus::Shipped;
+    msg!("Order {} marked shipped by {}", contract.key(), ctx.accounts.authority.key());
+    Ok(())
+}
@@ -2,22 +2,40 @@ mod upload_equipment;
 mod update_equipment;
 mod get_equipment;
 mod get_all_vendor_equipment;
-// mod sell_equipment;
+mod purchase_equipment;
 mod fund_equipment_for_listing;
 mod get_funded_equipment;
+mod get_funder_portfolio;
 mod fund_equipment_for_borrower_no_payment;
 mod fund_equipment_for_borrower_with_payment;
 mod confirm_delivery;
 mod confirm_funded_delivery;
+mod mark_shipped;
+mod cancel_order;
+mod order_dispute;
+mod adjust_stock;
+mod delist_equipment;
+mod close_equipment;
+mod reprice_funder_position;
+mod equipment_variant;
 
 pub use upload_equipment::*;
 pub use update_equipment::*;
 pub use get_equipment::*;
 pub use get_all_vendor_equipment::*;
-// pub use sell_equipment::*;
+pub use purchase_equipment::*;
 pub use fund_equipment_for_listing::*;
 pub use get_funded_equipment::*;
+pub use get_funder_portfolio::*;
 pub use fund_equipment_for_borrower_no_payment::*;
 pub use fund_equipment_for_borrower_with_payment::*;
 pub use confirm_delivery::*;
-pub use confirm_funded_delivery::*;
\ No newline at end of file
+pub use confirm_funded_delivery::*;
+pub use mark_shipped::*;
+pub use cancel_order::*;
+pub use order_dispute::*;
+pub use adjust_stock::*;
+pub use delist_equipment::*;
+pub use close_equipment::*;
+pub use reprice_funder_position::*;
+pub use equipment_variant::*;
\ No newline at end of file
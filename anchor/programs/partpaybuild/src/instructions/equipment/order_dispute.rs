@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use mpl_core::ID as MPL_CORE_ID;
+use crate::{
+    errors::ErrorCode,
+    state::{
+        borrower::Borrower,
+        config::ProgramConfig,
+        contract::{BNPLContract, ContractKind},
+        equipment::{DeliveryStatus, Equipment},
+        escrow::Escrow,
+        marketplace::Marketplace,
+        vendor::Vendor,
+    },
+    utils::{
+        orders::{complete_delivery, mint_unit_asset, refund_undelivered, unit_asset_accounts},
+        transfer::transfer_checked_received,
+    },
+};
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(mut, has_one = borrower)]
+    pub contract: Account<'info, BNPLContract>,
+    pub borrower: Signer<'info>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Freezes an undelivered order's escrow until a resolver rules on it.
+pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+    let contract = &mut ctx.accounts.contract;
+    require!(
+        matches!(contract.delivery_status, DeliveryStatus::Pending | DeliveryStatus::Shipped),
+        ErrorCode::InvalidDeliveryStatus
+    );
+    contract.delivery_status = DeliveryStatus::Disputed;
+    msg!("Dispute opened on order {}", contract.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        has_one = equipment,
+        has_one = escrow,
+        has_one = borrower,
+        constraint = contract.delivery_status == DeliveryStatus::Disputed @ ErrorCode::InvalidDeliveryStatus
+    )]
+    pub contract: Box<Account<'info, BNPLContract>>,
+    #[account(mut, has_one = vendor)]
+    pub equipment: Box<Account<'info, Equipment>>,
+    pub vendor: Box<Account<'info, Vendor>>,
+    #[account(
+        mut,
+        seeds = [
+            b"escrow",
+            equipment.key().as_ref(),
+            contract.borrower.as_ref(),
+            contract.contract_unique_id.as_ref()
+        ],
+        bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: Checked against `contract.borrower`; owner of the unit asset
+    pub borrower: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = contract.borrower
+    )]
+    pub borrower_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = contract.payee
+    )]
+    pub payee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: The borrower's profile PDA; its loan count is released in logic
+    #[account(
+        mut,
+        seeds = [Borrower::SEED_PREFIX, contract.borrower.as_ref()],
+        bump
+    )]
+    pub borrower_profile: UncheckedAccount<'info>,
+    /// CHECK: PDA for the first undelivered unit, checked in logic and
+    /// initialized by Metaplex Core. The remaining units' assets follow in the
+    /// remaining accounts.
+    #[account(mut)]
+    pub unit_asset: UncheckedAccount<'info>,
+    pub marketplace: Option<Box<Account<'info, Marketplace>>>,
+    /// The vendor's marketplace authority, or the program admin for vendors
+    /// outside a marketplace.
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+    #[account(address = contract.stablecoin_mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This is the Metaplex Core program
+    #[account(address = MPL_CORE_ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Settles a disputed order: either the escrow is refunded and the
+/// undelivered units cancelled, or it is released to the payee as if their
+/// delivery was confirmed.
+pub fn resolve_dispute<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+    refund_borrower: bool,
+) -> Result<()> {
+    let resolver = ctx.accounts.resolver.key();
+    let is_marketplace_authority = match ctx.accounts.vendor.marketplace {
+        Some(vendor_marketplace) => ctx
+            .accounts
+            .marketplace
+            .as_ref()
+            .is_some_and(|m| m.key() == vendor_marketplace && m.authority == resolver),
+        None => false,
+    };
+    require!(
+        is_marketplace_authority || resolver == ctx.accounts.config.admin,
+        ErrorCode::InvalidDisputeResolver
+    );
+    require!(!ctx.accounts.escrow.is_released, ErrorCode::FundsAlreadyReleased);
+    // A refund returns what is left in escrow, which is the undelivered units'
+    // share. Installments beyond the deposit went straight to the payee and
+    // cannot be unwound, so like a cancellation it needs none to have been paid.
+    if refund_borrower && ctx.accounts.contract.kind == ContractKind::Bnpl {
+        require!(ctx.accounts.contract.paid_installments <= 1, ErrorCode::OrderHasPayments);
+    }
+
+    let equipment_key = ctx.accounts.equipment.key();
+    let borrower = ctx.accounts.contract.borrower;
+    let unique_id = ctx.accounts.contract.contract_unique_id;
+    let escrow_seeds: &[&[u8]] = &[
+        b"escrow",
+        equipment_key.as_ref(),
+        borrower.as_ref(),
+        unique_id.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    let destination = if refund_borrower {
+        &mut ctx.accounts.borrower_token_account
+    } else {
+        &mut ctx.accounts.payee_token_account
+    };
+    let released = ctx.accounts.escrow.amount;
+    let received = transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.escrow_token_account.to_account_info(),
+        destination,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.escrow.to_account_info(),
+        &[escrow_seeds],
+        released,
+    )?;
+    ctx.accounts.escrow.is_released = true;
+
+    if refund_borrower {
+        refund_undelivered(&mut ctx.accounts.equipment, &mut ctx.accounts.contract, released)?;
+        if ctx.accounts.contract.is_completed {
+            Borrower::close_loan(
+                &ctx.accounts.borrower_profile,
+                ctx.program_id,
+                &mut ctx.accounts.contract.counts_toward_loans,
+            )?;
+        }
+        msg!("Dispute resolved for the borrower: {}", ctx.accounts.contract.key());
+    } else {
+        let contract_key = ctx.accounts.contract.key();
+        let first_offset = ctx.accounts.contract.delivered_quantity;
+        let units = ctx.accounts.contract.undelivered_quantity();
+        let unit_assets = unit_asset_accounts(
+            &ctx.accounts.unit_asset.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.program_id,
+            &contract_key,
+            first_offset,
+            units,
+        )?;
+        for (i, (unit_asset, bump)) in unit_assets.iter().enumerate() {
+            mint_unit_asset(
+                unit_asset,
+                *bump,
+                &contract_key,
+                first_offset + i as u64,
+                &ctx.accounts.equipment,
+                &ctx.accounts.resolver.to_account_info(),
+                &ctx.accounts.borrower.to_account_info(),
+                &ctx.accounts.vendor.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.mpl_core_program.to_account_info(),
+            )?;
+        }
+        complete_delivery(
+            &mut ctx.accounts.equipment,
+            &mut ctx.accounts.contract,
+            unit_assets[0].0.key(),
+            received,
+            units,
+        );
+        msg!("Dispute resolved for the payee: {}", contract_key);
+    }
+    Ok(())
+}
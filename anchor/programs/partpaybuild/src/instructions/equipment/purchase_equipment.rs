@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{
+    constants::CONTRACT_SEED,
+    errors::ErrorCode,
+    state::{
+        config::ProgramConfig,
+        contract::{BNPLContract, ContractKind, InstallmentFrequency},
+        currency::{settlement_rate, CurrencyFeed},
+        equipment::{DeliveryStatus, Equipment},
+        escrow::Escrow,
+        marketplace::{FeeKind, Marketplace},
+        vendor::Vendor,
+    },
+    utils::{fees::marketplace_fee, transfer::transfer_checked_received},
+};
+
+#[derive(Accounts)]
+#[instruction(order_unique_id: Pubkey)]
+pub struct PurchaseEquipment<'info> {
+    #[account(
+        init,
+        payer = buyer,
+        space = BNPLContract::LEN,
+        seeds = [CONTRACT_SEED, buyer.key().as_ref(), equipment.key().as_ref(), order_unique_id.as_ref()],
+        bump
+    )]
+    pub contract: Box<Account<'info, BNPLContract>>,
+    #[account(mut)]
+    pub equipment: Box<Account<'info, Equipment>>,
+    #[account(
+        constraint = vendor.key() == equipment.vendor @ ErrorCode::InvalidVendor,
+        constraint = vendor.is_active() @ ErrorCode::VendorNotActive
+    )]
+    pub vendor: Box<Account<'info, Vendor>>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(
+        address = equipment.mint @ ErrorCode::InvalidMint,
+        constraint = config.is_mint_allowed(&usdc_mint.key()) @ ErrorCode::MintNotAllowed
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = buyer
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = buyer,
+        space = Escrow::LEN,
+        seeds = [b"escrow", equipment.key().as_ref(), buyer.key().as_ref(), order_unique_id.as_ref()],
+        bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub marketplace: Option<Box<Account<'info, Marketplace>>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub currency_feed: Option<Box<Account<'info, CurrencyFeed>>>,
+    /// CHECK: Checked against `currency_feed.price_feed` before it is read
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_CONTRACTS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Buys `quantity` units of vendor stock for their full price. The payment sits in a
+/// per-order escrow until `confirm_delivery`, like a BNPL deposit, and the
+/// order is recorded as an `Outright` contract so it shares the delivery,
+/// cancellation and dispute instructions with BNPL orders.
+pub fn purchase_equipment(
+    ctx: Context<PurchaseEquipment>,
+    order_unique_id: Pubkey,
+    variant: u8,
+    quantity: u64,
+) -> Result<()> {
+    require!(quantity > 0, ErrorCode::InvalidQuantity);
+    let equipment = &mut ctx.accounts.equipment;
+    require!(!equipment.delisted, ErrorCode::EquipmentDelisted);
+    require!(equipment.accepts_full_payment(), ErrorCode::BnplOnly);
+
+    let sku = equipment.variant(variant)?;
+    require!(
+        (sku.total_quantity - sku.funded_quantity).saturating_sub(sku.sold_quantity) >= quantity,
+        ErrorCode::OutOfStock
+    );
+    let unit_price = sku.price;
+    let listed_price = unit_price.checked_mul(quantity).ok_or(ErrorCode::MathOverflow)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let fx_rate = settlement_rate(
+        equipment.reference_currency,
+        &ctx.accounts.usdc_mint.key(),
+        ctx.accounts.currency_feed.as_deref().map(|feed| &**feed),
+        ctx.accounts.price_feed.as_ref().map(|feed| feed.as_ref()),
+        now,
+    )?;
+    let price = match &fx_rate {
+        Some(rate) => rate.to_settlement(listed_price, ctx.accounts.usdc_mint.decimals)?,
+        None => listed_price,
+    };
+    if let Some(vendor_marketplace) = ctx.accounts.vendor.marketplace {
+        require!(
+            ctx.accounts.marketplace.as_ref().is_some_and(|m| m.key() == vendor_marketplace),
+            ErrorCode::MarketplaceRequired
+        );
+    }
+
+    let fee = marketplace_fee(
+        &ctx.accounts.vendor,
+        ctx.accounts.marketplace.as_deref(),
+        ctx.accounts.treasury_token_account.as_deref(),
+        &ctx.accounts.usdc_mint.key(),
+        FeeKind::Deposit,
+        price,
+    )?;
+    let received = transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.buyer_token_account.to_account_info(),
+        &mut ctx.accounts.escrow_token_account,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.buyer.to_account_info(),
+        &[],
+        price - fee,
+    )?;
+    if fee > 0 {
+        let treasury = ctx.accounts.treasury_token_account.as_mut().ok_or(ErrorCode::InvalidTreasury)?;
+        transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.buyer_token_account.to_account_info(),
+            treasury,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.buyer.to_account_info(),
+            &[],
+            fee,
+        )?;
+    }
+
+    let buyer_key = ctx.accounts.buyer.key();
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.equipment = equipment.key();
+    escrow.funder = buyer_key;
+    escrow.vendor = equipment.vendor;
+    escrow.amount = received;
+    escrow.is_released = false;
+
+    equipment.variant_mut(variant)?.sold_quantity += quantity;
+    equipment.sold_quantity += quantity;
+    equipment.open_escrows += 1;
+    equipment.refresh_status();
+
+    let contract = &mut ctx.accounts.contract;
+    contract.borrower = buyer_key;
+    contract.payee = equipment.vendor;
+    contract.equipment = equipment.key();
+    contract.equipment_unit_index = equipment.issue_units(quantity)?;
+    contract.total_amount = price;
+    contract.amount_paid = price;
+    contract.deposit = price;
+    contract.start_date = now;
+    contract.end_date = now;
+    contract.contract_unique_id = order_unique_id;
+    contract.last_payment_date = now;
+    contract.installment_count = 0;
+    contract.paid_installments = 0;
+    contract.installment_frequency = InstallmentFrequency::Custom { seconds: 0 };
+    contract.is_completed = true;
+    contract.insurance_premium = None;
+    contract.is_insured = false;
+    contract.credit_score_delta = 0;
+    contract.stablecoin_mint = ctx.accounts.usdc_mint.key();
+    contract.escrow = escrow.key();
+    contract.funder_escrow = None;
+    contract.fx_rate = fx_rate;
+    contract.unit_price = unit_price;
+    contract.terms_version = equipment.terms_version;
+    contract.variant = variant;
+    contract.kind = ContractKind::Outright;
+    contract.delivery_status = DeliveryStatus::Pending;
+    contract.unit_asset = None;
+    contract.quantity = quantity;
+    contract.delivered_quantity = 0;
+    contract.counts_toward_loans = false;
+
+    msg!("Equipment purchased outright: {}", contract.key());
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    errors::ErrorCode,
+    state::{config::ProgramConfig, equipment::Equipment, escrow::Escrow, vendor::Vendor},
+    utils::transfer::{gross_up_for_transfer_fee, transfer_checked_received},
+};
+
+#[derive(Accounts)]
+#[instruction(escrow_owner: Pubkey, unique_id: Pubkey)]
+pub struct RepriceFunderPosition<'info> {
+    #[account(mut, has_one = vendor)]
+    pub equipment: Box<Account<'info, Equipment>>,
+    pub vendor: Box<Account<'info, Vendor>>,
+    #[account(
+        mut,
+        seeds = [b"escrow", equipment.key().as_ref(), escrow_owner.as_ref(), unique_id.as_ref()],
+        bump,
+        has_one = equipment
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = escrow.funder
+    )]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The funder, or the vendor authority when the price went down.
+    pub signer: Signer<'info>,
+    #[account(address = equipment.mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_FUNDING) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Moves a funder position onto its variant's current price for its unsold
+/// units. A price drop refunds the difference from the escrow and may be
+/// triggered by the funder or the vendor; a price rise is a top-up and needs
+/// the funder's signature.
+pub fn reprice_funder_position(
+    ctx: Context<RepriceFunderPosition>,
+    escrow_owner: Pubkey,
+    unique_id: Pubkey,
+) -> Result<()> {
+    let escrow_key = ctx.accounts.escrow.key();
+    let signer_key = ctx.accounts.signer.key();
+    let is_funder = signer_key == ctx.accounts.escrow.funder;
+    require!(
+        is_funder || signer_key == ctx.accounts.vendor.authority,
+        ErrorCode::Unauthorized
+    );
+    require!(!ctx.accounts.escrow.is_released, ErrorCode::FundsAlreadyReleased);
+
+    let equipment = &ctx.accounts.equipment;
+    let terms_version = equipment.terms_version;
+    let funder_info = equipment
+        .funders
+        .iter()
+        .find(|f| f.escrow == escrow_key)
+        .ok_or(ErrorCode::InvalidEscrow)?;
+    require!(funder_info.terms_version != terms_version, ErrorCode::TermsUpToDate);
+    let new_price = equipment.variant(funder_info.variant)?.price;
+    let old_price = funder_info.unit_price;
+    let unsold = funder_info.unsold_units();
+
+    if new_price < old_price {
+        let refund = (old_price - new_price)
+            .checked_mul(unsold)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(refund <= ctx.accounts.escrow.amount, ErrorCode::InsufficientFunds);
+        let equipment_key = ctx.accounts.equipment.key();
+        transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.escrow_token_account.to_account_info(),
+            &mut ctx.accounts.funder_token_account,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.escrow.to_account_info(),
+            &[&[
+                b"escrow",
+                equipment_key.as_ref(),
+                escrow_owner.as_ref(),
+                unique_id.as_ref(),
+                &[ctx.bumps.escrow],
+            ]],
+            refund,
+        )?;
+        ctx.accounts.escrow.amount -= refund;
+        msg!("Refunded {} to funder", refund);
+    } else if new_price > old_price {
+        require!(is_funder, ErrorCode::Unauthorized);
+        let top_up = (new_price - old_price)
+            .checked_mul(unsold)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let gross = gross_up_for_transfer_fee(&ctx.accounts.usdc_mint, top_up)?;
+        let received = transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.funder_token_account.to_account_info(),
+            &mut ctx.accounts.escrow_token_account,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.signer.to_account_info(),
+            &[],
+            gross,
+        )?;
+        require!(received >= top_up, ErrorCode::InsufficientFunds);
+        ctx.accounts.escrow.amount = ctx
+            .accounts
+            .escrow
+            .amount
+            .checked_add(received)
+            .ok_or(ErrorCode::MathOverflow)?;
+        msg!("Funder topped up {}", received);
+    }
+
+    let funder_info = ctx
+        .accounts
+        .equipment
+        .funders
+        .iter_mut()
+        .find(|f| f.escrow == escrow_key)
+        .ok_or(ErrorCode::InvalidEscrow)?;
+    // Sold units keep the price they were sold at; only the unsold ones move.
+    let unsold_principal = new_price as u128 * unsold as u128;
+    funder_info.amount_funded = u64::try_from(funder_info.sold_principal as u128 + unsold_principal)
+        .map_err(|_| error!(ErrorCode::MathOverflow))?;
+    funder_info.unit_price = new_price;
+    funder_info.terms_version = terms_version;
+
+    msg!("Funder position moved to terms version {}", terms_version);
+    Ok(())
+}
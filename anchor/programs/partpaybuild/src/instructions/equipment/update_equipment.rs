@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use mpl_core::{instructions::UpdateV1Builder, ID as MPL_CORE_ID};
 use crate::{
-    state::{equipment::Equipment, vendor::Vendor},
+    state::{config::ProgramConfig, delegate::VendorDelegate, equipment::Equipment, vendor::Vendor},
     errors::ErrorCode,
     utils::validation::{validate_name, validate_uri, validate_price},
 };
@@ -13,14 +15,37 @@ pub struct UpdateEquipment<'info> {
         constraint = equipment.vendor == vendor.key()
     )]
     pub equipment: Account<'info, Equipment>,
+    /// CHECK: Checked against `equipment.asset`, updated by Metaplex Core
+    #[account(mut, address = equipment.asset @ ErrorCode::InvalidEquipment)]
+    pub equipment_asset: UncheckedAccount<'info>,
     
+    #[account(has_one = collection @ ErrorCode::InvalidVendor)]
     pub vendor: Account<'info, Vendor>,
+    /// CHECK: Checked against `vendor.collection`, read by Metaplex Core
+    #[account(mut)]
+    pub collection: UncheckedAccount<'info>,
     
     #[account(
-        constraint = authority.key() == vendor.authority,
-        constraint = authority.key() == vendor.authority @ ErrorCode::InvalidMarketplaceAuthority
+        mut,
+        constraint = vendor.authorizes(
+            &vendor.key(),
+            &authority.key(),
+            vendor_delegate.as_deref(),
+            VendorDelegate::CATALOG
+        ) @ ErrorCode::MissingVendorPermission
     )]
     pub authority: Signer<'info>,
+    pub vendor_delegate: Option<Account<'info, VendorDelegate>>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This is the Metaplex Core program
+    #[account(address = MPL_CORE_ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
 }
 
 pub fn update_equipment(
@@ -30,28 +55,76 @@ pub fn update_equipment(
     price: Option<u64>,
 ) -> Result<()> {
     msg!("Starting update_equipment function");
-    
+
+    if let Some(new_name) = &name {
+        validate_name(new_name)?;
+    }
+    if let Some(new_uri) = &uri {
+        validate_uri(new_uri)?;
+    }
+
+    // Keep the Core asset's name and URI in step with the listing. The vendor
+    // PDA is the collection's update authority, so it signs for catalog
+    // delegates as well as the vendor authority.
+    if name.is_some() || uri.is_some() {
+        let mut builder = UpdateV1Builder::new();
+        builder
+            .asset(ctx.accounts.equipment_asset.key())
+            .collection(Some(ctx.accounts.collection.key()))
+            .payer(ctx.accounts.authority.key())
+            .authority(Some(ctx.accounts.vendor.key()))
+            .system_program(ctx.accounts.system_program.key());
+        if let Some(new_name) = &name {
+            builder.new_name(new_name.clone());
+        }
+        if let Some(new_uri) = &uri {
+            builder.new_uri(new_uri.clone());
+        }
+
+        invoke_signed(
+            &builder.instruction(),
+            &[
+                ctx.accounts.equipment_asset.to_account_info(),
+                ctx.accounts.collection.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.vendor.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.mpl_core_program.to_account_info(),
+            ],
+            &[&ctx.accounts.vendor.signer_seeds()],
+        )
+        .map_err(|e| {
+            msg!("Error invoking Metaplex Core program: {:?}", e);
+            error!(ErrorCode::MetaplexError)
+        })?;
+    }
+
     let equipment = &mut ctx.accounts.equipment;
 
-    // Update and validate name if provided
     if let Some(new_name) = name {
-        validate_name(&new_name)?;
         equipment.name = new_name;
         msg!("Updated equipment name");
     }
 
-    // Update and validate URI if provided
     if let Some(new_uri) = uri {
-        validate_uri(&new_uri)?;
         equipment.uri = new_uri;
         msg!("Updated equipment URI");
     }
 
     // Update and validate price if provided
+    // Only future sales and fundings take the new price; existing positions
+    // move over through `reprice_funder_position`.
     if let Some(new_price) = price {
         validate_price(new_price)?;
-        equipment.price = new_price;
-        msg!("Updated equipment price");
+        if new_price != equipment.price {
+            equipment.price = new_price;
+            equipment.variant_mut(0)?.price = new_price;
+            equipment.terms_version = equipment
+                .terms_version
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+            msg!("Updated equipment price, terms version {}", equipment.terms_version);
+        }
     }
 
     msg!("Equipment update completed successfully");
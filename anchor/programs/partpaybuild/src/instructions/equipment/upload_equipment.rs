@@ -1,12 +1,18 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::Mint;
 use mpl_core::{instructions::CreateV2Builder, types::DataState, ID as MPL_CORE_ID};
 
 use crate::{
     constants::EQUIPMENT_SEED,
-    state::equipment::{Equipment, EquipmentStatus, PaymentPreference},
+    state::equipment::{Equipment, EquipmentStatus, EquipmentVariant, PaymentPreference},
     state::vendor::Vendor,
-    utils::validation::{validate_duration, validate_price},
+    state::config::ProgramConfig,
+    state::currency::CurrencyFeed,
+    state::delegate::VendorDelegate,
+    state::catalog::VendorCatalogPage,
+    state::category::{CategoryIndexPage, EquipmentCategory},
+    utils::validation::{validate_duration, validate_name, validate_price},
     errors::ErrorCode,
 };
 
@@ -26,7 +32,7 @@ pub struct UploadEquipment<'info> {
         init,
         payer = payer,
         space = Equipment::LEN,
-        seeds = [EQUIPMENT_SEED, vendor.key().as_ref(), unique_id.as_ref(), name.as_bytes()],
+        seeds = [EQUIPMENT_SEED, vendor.key().as_ref(), &vendor.catalog_slots.to_le_bytes()],
         bump
     )]
     pub equipment: Account<'info, Equipment>,
@@ -37,20 +43,73 @@ pub struct UploadEquipment<'info> {
         bump
     )]
     pub equipment_asset: UncheckedAccount<'info>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vendor.is_active() @ ErrorCode::VendorNotActive
+    )]
     pub vendor: Account<'info, Vendor>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VendorCatalogPage::LEN,
+        seeds = [
+            VendorCatalogPage::SEED_PREFIX,
+            vendor.key().as_ref(),
+            &VendorCatalogPage::page_for_slot(vendor.catalog_slots).to_le_bytes()
+        ],
+        bump
+    )]
+    pub catalog_page: Box<Account<'info, VendorCatalogPage>>,
+    #[account(
+        mut,
+        constraint = Some(category.marketplace) == vendor.marketplace @ ErrorCode::CategoryNotInMarketplace
+    )]
+    pub category: Option<Box<Account<'info, EquipmentCategory>>>,
+    /// Seeds come from `category`; passing the page without it is an error.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = CategoryIndexPage::LEN,
+        seeds = [
+            CategoryIndexPage::SEED_PREFIX,
+            category.as_ref().ok_or(error!(ErrorCode::InvalidCategory))?.key().as_ref(),
+            &CategoryIndexPage::page_for_slot(
+                category.as_ref().ok_or(error!(ErrorCode::InvalidCategory))?.slots
+            ).to_le_bytes()
+        ],
+        bump
+    )]
+    pub category_page: Option<Box<Account<'info, CategoryIndexPage>>>,
     /// CHECK: This account is managed by the Metaplex Core program
     #[account(mut)]
     pub vendor_collection: UncheckedAccount<'info>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vendor.authorizes(
+            &vendor.key(),
+            &authority.key(),
+            vendor_delegate.as_deref(),
+            VendorDelegate::CATALOG
+        ) @ ErrorCode::MissingVendorPermission
+    )]
     pub authority: Signer<'info>,
+    pub vendor_delegate: Option<Account<'info, VendorDelegate>>,
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(constraint = config.is_mint_allowed(&usdc_mint.key()) @ ErrorCode::MintNotAllowed)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub currency_feed: Option<Account<'info, CurrencyFeed>>,
     pub system_program: Program<'info, System>,
     /// CHECK: This is the Metaplex Core program
     #[account(address = MPL_CORE_ID)]
     pub mpl_core_program: UncheckedAccount<'info>,
     pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
 }
 
 pub fn upload_equipment(
@@ -63,17 +122,40 @@ pub fn upload_equipment(
     minimum_deposit: u64,
     max_duration_seconds: i64,
     payment_preference: PaymentPreference,
+    reference_currency: Option<[u8; 3]>,
+    tags: Vec<String>,
 ) -> Result<()> {
     msg!("Starting upload_equipment function");
 
+    validate_name(&name)?;
     validate_price(price)?;
     validate_price(minimum_deposit)?;
     validate_duration(max_duration_seconds)?;
+    if let PaymentPreference::Both { timeout } = payment_preference {
+        require!(timeout > 0, ErrorCode::InvalidPaymentPreference);
+    }
+    require!(
+        tags.len() <= Equipment::MAX_TAGS
+            && tags.iter().all(|tag| !tag.is_empty() && tag.len() <= Equipment::MAX_TAG_LEN),
+        ErrorCode::InvalidTags
+    );
+    require!(
+        ctx.accounts.category.is_some() == ctx.accounts.category_page.is_some(),
+        ErrorCode::InvalidCategory
+    );
+    if let Some(currency) = reference_currency {
+        require!(
+            ctx.accounts.currency_feed.as_ref().is_some_and(|feed| {
+                feed.currency == currency && feed.mint == ctx.accounts.usdc_mint.key()
+            }),
+            ErrorCode::CurrencyFeedRequired
+        );
+    }
 
     let create_equipment_ix = CreateV2Builder::new()
     .asset(ctx.accounts.equipment_asset.key())
     .collection(Some(ctx.accounts.vendor_collection.key()))
-    .authority(Some(ctx.accounts.authority.key()))
+    .authority(Some(ctx.accounts.vendor.key()))
     .payer(ctx.accounts.payer.key())
     .owner(Some(ctx.accounts.vendor.key()))
     .system_program(ctx.accounts.system_program.key())
@@ -91,7 +173,8 @@ pub fn upload_equipment(
         &[ctx.bumps.equipment_asset],
     ];
 
-    let signer_seeds = &[&asset_seeds[..]];
+    let vendor_seeds = ctx.accounts.vendor.signer_seeds();
+    let signer_seeds = &[&asset_seeds[..], &vendor_seeds[..]];
 
     invoke_signed(
         &create_equipment_ix,
@@ -115,6 +198,7 @@ pub fn upload_equipment(
     let equipment = &mut ctx.accounts.equipment;  
     equipment.vendor = ctx.accounts.vendor.key();
     equipment.asset = ctx.accounts.equipment_asset.key();
+    equipment.mint = ctx.accounts.usdc_mint.key();
     equipment.unique_id = unique_id;
     equipment.name = name.clone();
     equipment.uri = uri.clone();
@@ -128,8 +212,48 @@ pub fn upload_equipment(
     equipment.funded_sold_quantity = 0;
     equipment.status = EquipmentStatus::Available;
     equipment.funders = Vec::new();
+    equipment.reference_currency = reference_currency;
+    equipment.catalog_slot = ctx.accounts.vendor.catalog_slots;
+    equipment.delisted = false;
+    equipment.active_contracts = 0;
+    equipment.open_escrows = 0;
+    equipment.terms_version = 0;
+    equipment.variants = vec![EquipmentVariant {
+        name: name.clone(),
+        price,
+        minimum_deposit,
+        total_quantity,
+        funded_quantity: 0,
+        sold_quantity: 0,
+        funded_sold_quantity: 0,
+    }];
+
+    equipment.tags = tags;
+    equipment.listed_at = Clock::get()?.unix_timestamp;
+    equipment.category = None;
+    equipment.category_slot = 0;
+    equipment.units_issued = 0;
+    if let (Some(category), Some(category_page)) =
+        (ctx.accounts.category.as_mut(), ctx.accounts.category_page.as_mut())
+    {
+        equipment.category = Some(category.key());
+        equipment.category_slot = category.slots;
+        category_page.category = category.key();
+        category_page.page = CategoryIndexPage::page_for_slot(category.slots);
+        category_page.bump = ctx.bumps.category_page.ok_or(ErrorCode::InvalidCategory)?;
+        category_page.entries.push(equipment.key());
+        category.slots += 1;
+        category.listing_count += 1;
+    }
+
+    let catalog_page = &mut ctx.accounts.catalog_page;
+    catalog_page.vendor = ctx.accounts.vendor.key();
+    catalog_page.page = VendorCatalogPage::page_for_slot(ctx.accounts.vendor.catalog_slots);
+    catalog_page.bump = ctx.bumps.catalog_page;
+    catalog_page.entries.push(equipment.key());
 
-    ctx.accounts.vendor.equipments.push(equipment.key()); 
-    ctx.accounts.vendor.equipment_count += total_quantity;
+    ctx.accounts.vendor.catalog_slots += 1;
+    ctx.accounts.vendor.listing_count += 1;
+    ctx.accounts.vendor.unit_count += total_quantity;
     Ok(())
 }
\ No newline at end of file
@@ -1,12 +1,32 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke_signed;
-use mpl_core::instructions::CreateCollectionV2Builder;
+use mpl_core::{
+    instructions::{AddCollectionPluginV1Builder, CreateCollectionV2Builder},
+    types::{Attribute, Attributes, Plugin, PluginAuthority},
+};
 
 use crate::{
+    constants::{MARKETPLACE_SEED, MAX_FEE_BPS},
     errors::ErrorCode,
-    utils::validation::{validate_name, validate_uri},
+    state::{
+        category::{CategoryEquipmentInfo, CategoryEquipmentResponse, EquipmentCategory},
+        equipment::Equipment,
+        marketplace::{
+            ApplicationStatus, MarketplaceEquipmentInfo, MarketplaceEquipmentResponse,
+            MarketplaceFees, MarketplaceRules,
+        },
+        vendor::Vendor,
+    },
+    utils::{
+        transfer::transfer_checked_received,
+        validation::{validate_name, validate_uri},
+    },
+};
+use super::structs::{
+    ApplyToMarketplace, ApproveVendor, CreateEquipmentCategory, CreateMarketplace, GetEquipmentByCategory,
+    GetMarketplaceEquipment, InitializeTreasury, ReviewVendorApplication, UpdateMarketplaceFees,
+    UpdateMarketplaceRules, WithdrawFees,
 };
-use super::structs::CreateMarketplace;
 
 pub fn create_marketplace(
     ctx: Context<CreateMarketplace>,
@@ -59,7 +79,277 @@ pub fn create_marketplace(
     marketplace.collection = ctx.accounts.marketplace_collection.key();
     marketplace.name = name;
     marketplace.uri = uri;
+    marketplace.vendor_count = 0;
+    marketplace.rules = MarketplaceRules::default();
+    marketplace.fees = MarketplaceFees::default();
 
     msg!("Marketplace created successfully");
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn apply_to_marketplace(ctx: Context<ApplyToMarketplace>) -> Result<()> {
+    let application = &mut ctx.accounts.application;
+    application.marketplace = ctx.accounts.marketplace.key();
+    application.vendor = ctx.accounts.vendor.key();
+    application.collection = ctx.accounts.vendor.collection;
+    application.status = ApplicationStatus::Pending;
+    application.applied_at = Clock::get()?.unix_timestamp;
+    application.bump = ctx.bumps.application;
+
+    msg!("Vendor {} applied to marketplace {}", application.vendor, application.marketplace);
+    Ok(())
+}
+
+/// Approves the application and tags the vendor's Core collection with the
+/// marketplace. The tag is held by the vendor PDA, so only the program can
+/// set it.
+pub fn approve_vendor(ctx: Context<ApproveVendor>) -> Result<()> {
+    let application = &mut ctx.accounts.application;
+    require!(application.status == ApplicationStatus::Pending, ErrorCode::ApplicationNotPending);
+    require!(ctx.accounts.vendor.marketplace.is_none(), ErrorCode::VendorAlreadyInMarketplace);
+
+    let vendor = &ctx.accounts.vendor;
+    let tag_collection_ix = AddCollectionPluginV1Builder::new()
+        .collection(ctx.accounts.collection.key())
+        .payer(ctx.accounts.authority.key())
+        .authority(Some(vendor.key()))
+        .system_program(ctx.accounts.system_program.key())
+        .plugin(Plugin::Attributes(Attributes {
+            attribute_list: vec![Attribute {
+                key: "marketplace".to_string(),
+                value: ctx.accounts.marketplace.key().to_string(),
+            }],
+        }))
+        .init_authority(PluginAuthority::UpdateAuthority)
+        .instruction();
+
+    invoke_signed(
+        &tag_collection_ix,
+        &[
+            ctx.accounts.collection.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            vendor.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.mpl_core_program.to_account_info(),
+        ],
+        &[&vendor.signer_seeds()],
+    )
+    .map_err(|e| {
+        msg!("Error invoking Metaplex Core program: {:?}", e);
+        error!(ErrorCode::MetaplexError)
+    })?;
+
+    application.status = ApplicationStatus::Approved;
+    ctx.accounts.vendor.marketplace = Some(ctx.accounts.marketplace.key());
+    ctx.accounts.marketplace.vendor_count = ctx
+        .accounts
+        .marketplace
+        .vendor_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Vendor approved: {}", ctx.accounts.vendor.key());
+    Ok(())
+}
+
+pub fn reject_vendor(ctx: Context<ReviewVendorApplication>) -> Result<()> {
+    let application = &ctx.accounts.application;
+    require!(application.status == ApplicationStatus::Pending, ErrorCode::ApplicationNotPending);
+
+    application.close(ctx.accounts.vendor_authority.to_account_info())?;
+
+    msg!("Vendor rejected: {}", ctx.accounts.vendor.key());
+    Ok(())
+}
+
+pub fn update_marketplace_rules(
+    ctx: Context<UpdateMarketplaceRules>,
+    rules: MarketplaceRules,
+) -> Result<()> {
+    ctx.accounts.marketplace.rules = rules;
+    msg!("Marketplace rules updated");
+    Ok(())
+}
+
+/// Remaining accounts are the marketplace's `Vendor` accounts followed by the
+/// `Equipment` accounts to list. Equipment of vendors not approved into this
+/// marketplace is skipped.
+pub fn get_marketplace_equipment<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetMarketplaceEquipment<'info>>
+) -> Result<MarketplaceEquipmentResponse> {
+    let marketplace_key = ctx.accounts.marketplace.key();
+    let mut vendors: Vec<Pubkey> = Vec::new();
+    let mut equipment_list: Vec<MarketplaceEquipmentInfo> = Vec::new();
+
+    for account_info in ctx.remaining_accounts.iter() {
+        if let Ok(vendor) = Account::<Vendor>::try_from(account_info) {
+            if vendor.marketplace == Some(marketplace_key) {
+                vendors.push(vendor.key());
+            }
+        } else if let Ok(equipment) = Account::<Equipment>::try_from(account_info) {
+            if vendors.contains(&equipment.vendor) {
+                let (min_price, max_price) = equipment.price_range();
+                equipment_list.push(MarketplaceEquipmentInfo {
+                    equipment_pda: equipment.key(),
+                    vendor: equipment.vendor,
+                    name: equipment.name.clone(),
+                    price: equipment.price,
+                    uri: equipment.uri.clone(),
+                    asset: equipment.asset,
+                    variant_count: equipment.variants.len() as u8,
+                    min_price,
+                    max_price,
+                    available_for_bnpl: equipment.available_for_bnpl(),
+                });
+            }
+        }
+    }
+
+    Ok(MarketplaceEquipmentResponse {
+        total_count: equipment_list.len() as u64,
+        equipment: equipment_list,
+    })
+}
+
+pub fn update_marketplace_fees(
+    ctx: Context<UpdateMarketplaceFees>,
+    fees: MarketplaceFees,
+) -> Result<()> {
+    require!(
+        fees.deposit_fee_bps <= MAX_FEE_BPS
+            && fees.installment_fee_bps <= MAX_FEE_BPS
+            && fees.funding_fee_bps <= MAX_FEE_BPS,
+        ErrorCode::FeeTooHigh
+    );
+    ctx.accounts.marketplace.fees = fees;
+    msg!("Marketplace fees updated");
+    Ok(())
+}
+
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+    msg!(
+        "Treasury {} initialized for mint {}",
+        ctx.accounts.treasury_token_account.key(),
+        ctx.accounts.usdc_mint.key()
+    );
+    Ok(())
+}
+
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        ctx.accounts.treasury_token_account.amount >= amount,
+        ErrorCode::InsufficientTreasuryBalance
+    );
+
+    let authority_key = ctx.accounts.authority.key();
+    transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.treasury_token_account.to_account_info(),
+        &mut ctx.accounts.destination_token_account,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.marketplace.to_account_info(),
+        &[&[
+            MARKETPLACE_SEED,
+            authority_key.as_ref(),
+            &[ctx.bumps.marketplace],
+        ]],
+        amount,
+    )?;
+
+    msg!("Withdrew {} in fees", amount);
+    Ok(())
+}
+
+pub fn create_equipment_category(
+    ctx: Context<CreateEquipmentCategory>,
+    slug: String,
+    name: String,
+) -> Result<()> {
+    require!(
+        !slug.is_empty()
+            && slug.len() <= EquipmentCategory::MAX_SLUG_LEN
+            && slug.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'),
+        ErrorCode::InvalidCategorySlug
+    );
+    validate_name(&name)?;
+
+    let category = &mut ctx.accounts.category;
+    category.marketplace = ctx.accounts.marketplace.key();
+    category.slug = slug;
+    category.name = name;
+    category.listing_count = 0;
+    category.slots = 0;
+    category.bump = ctx.bumps.category;
+    msg!("Category created: {}", category.slug);
+    Ok(())
+}
+
+/// Reads one page of a category index. Delisted listings are left out, and
+/// `tag` narrows the page to listings carrying that tag.
+pub fn get_equipment_by_category<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetEquipmentByCategory<'info>>,
+    _page: u32,
+    tag: Option<String>,
+) -> Result<CategoryEquipmentResponse> {
+    let category_key = ctx.accounts.category.key();
+    let now = Clock::get()?.unix_timestamp;
+    let category_page = &ctx.accounts.category_page;
+    let mut response = CategoryEquipmentResponse {
+        listing_count: ctx.accounts.category.listing_count,
+        equipment: Vec::new(),
+        failed_loads: Vec::new(),
+    };
+
+    let first_slot = category_page.first_slot();
+    for (offset, entry) in category_page.entries.iter().enumerate() {
+        let slot = first_slot + offset as u64;
+        if *entry == Pubkey::default() {
+            continue;
+        }
+        let equipment = match ctx.remaining_accounts.get(offset) {
+            Some(account_info) if account_info.key == entry => {
+                match Account::<Equipment>::try_from(account_info) {
+                    Ok(equipment) if equipment.category == Some(category_key) => equipment,
+                    _ => {
+                        response.failed_loads.push(slot);
+                        continue;
+                    }
+                }
+            }
+            _ => {
+                response.failed_loads.push(slot);
+                continue;
+            }
+        };
+        if equipment.delisted {
+            continue;
+        }
+        if let Some(tag) = &tag {
+            if !equipment.tags.contains(tag) {
+                continue;
+            }
+        }
+
+        let (min_price, max_price) = equipment.price_range();
+        response.equipment.push(CategoryEquipmentInfo {
+            equipment_pda: equipment.key(),
+            vendor: equipment.vendor,
+            name: equipment.name.clone(),
+            uri: equipment.uri.clone(),
+            asset: equipment.asset,
+            tags: equipment.tags.clone(),
+            status: equipment.status.clone(),
+            available_for_bnpl: equipment.available_for_bnpl(),
+            available_for_funding: equipment.available_for_funding(),
+            min_price,
+            max_price,
+            minimum_deposit: equipment.minimum_deposit,
+            max_duration_seconds: equipment.max_duration_seconds,
+            payment_preference: equipment.effective_payment_preference(now),
+            reference_currency: equipment.reference_currency,
+        });
+    }
+
+    Ok(response)
+}
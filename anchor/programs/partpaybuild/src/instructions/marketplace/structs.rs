@@ -1,5 +1,16 @@
 use anchor_lang::prelude::*;
-use crate::state::marketplace::Marketplace;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use mpl_core::ID as MPL_CORE_ID;
+use crate::{
+    constants::{MARKETPLACE_SEED, TREASURY_SEED},
+    errors::ErrorCode,
+    state::{
+        config::ProgramConfig,
+        category::{CategoryIndexPage, EquipmentCategory},
+        marketplace::{Marketplace, MarketplaceApplication},
+        vendor::Vendor,
+    },
+};
 
 #[derive(Accounts)]
 #[instruction(name: String, uri: String)]
@@ -7,7 +18,7 @@ pub struct CreateMarketplace<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 32 + 64 + 64,
+        space = Marketplace::LEN,
         seeds = [crate::constants::MARKETPLACE_SEED, authority.key().as_ref()],
         bump
     )]
@@ -37,4 +48,273 @@ pub struct CreateMarketplace<'info> {
     pub mpl_core_program: UncheckedAccount<'info>,
 
     pub rent: Sysvar<'info, Rent>,
-}
\ No newline at end of file
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyToMarketplace<'info> {
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        has_one = authority,
+        constraint = vendor.marketplace.is_none() @ ErrorCode::VendorAlreadyInMarketplace,
+        constraint = vendor.is_active() @ ErrorCode::VendorNotActive
+    )]
+    pub vendor: Account<'info, Vendor>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = MarketplaceApplication::LEN,
+        seeds = [
+            MarketplaceApplication::SEED_PREFIX,
+            marketplace.key().as_ref(),
+            vendor.key().as_ref()
+        ],
+        bump
+    )]
+    pub application: Account<'info, MarketplaceApplication>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewVendorApplication<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::InvalidMarketplaceAuthority)]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(mut)]
+    pub vendor: Account<'info, Vendor>,
+
+    #[account(
+        mut,
+        has_one = marketplace,
+        has_one = vendor,
+        seeds = [
+            MarketplaceApplication::SEED_PREFIX,
+            marketplace.key().as_ref(),
+            vendor.key().as_ref()
+        ],
+        bump = application.bump
+    )]
+    pub application: Account<'info, MarketplaceApplication>,
+
+    /// CHECK: Receives the application rent on rejection
+    #[account(mut, address = vendor.authority @ ErrorCode::InvalidVendor)]
+    pub vendor_authority: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveVendor<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::InvalidMarketplaceAuthority)]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(mut, has_one = collection @ ErrorCode::InvalidVendor)]
+    pub vendor: Account<'info, Vendor>,
+
+    #[account(
+        mut,
+        has_one = marketplace,
+        has_one = vendor,
+        seeds = [
+            MarketplaceApplication::SEED_PREFIX,
+            marketplace.key().as_ref(),
+            vendor.key().as_ref()
+        ],
+        bump = application.bump
+    )]
+    pub application: Account<'info, MarketplaceApplication>,
+
+    /// CHECK: Checked against `vendor.collection`, tagged by Metaplex Core
+    #[account(mut)]
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: This is the Metaplex Core program
+    #[account(address = MPL_CORE_ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMarketplaceRules<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::InvalidMarketplaceAuthority)]
+    pub marketplace: Account<'info, Marketplace>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct GetMarketplaceEquipment<'info> {
+    pub marketplace: Account<'info, Marketplace>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMarketplaceFees<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::InvalidMarketplaceAuthority)]
+    pub marketplace: Account<'info, Marketplace>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(has_one = authority @ ErrorCode::InvalidMarketplaceAuthority)]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = marketplace,
+        seeds = [TREASURY_SEED, marketplace.key().as_ref(), usdc_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        has_one = authority @ ErrorCode::InvalidMarketplaceAuthority,
+        seeds = [MARKETPLACE_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        token::authority = marketplace,
+        seeds = [TREASURY_SEED, marketplace.key().as_ref(), treasury_token_account.mint.as_ref()],
+        bump
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = treasury_token_account.mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+#[derive(Accounts)]
+#[instruction(slug: String)]
+pub struct CreateEquipmentCategory<'info> {
+    #[account(has_one = authority @ ErrorCode::InvalidMarketplaceAuthority)]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = EquipmentCategory::LEN,
+        seeds = [EquipmentCategory::SEED_PREFIX, marketplace.key().as_ref(), slug.as_bytes()],
+        bump
+    )]
+    pub category: Account<'info, EquipmentCategory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_LISTINGS) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Remaining accounts are the equipment accounts for the page's entries, in
+/// order, as with `get_all_vendor_equipment`.
+#[derive(Accounts)]
+#[instruction(page: u32)]
+pub struct GetEquipmentByCategory<'info> {
+    pub category: Account<'info, EquipmentCategory>,
+
+    #[account(
+        seeds = [CategoryIndexPage::SEED_PREFIX, category.key().as_ref(), &page.to_le_bytes()],
+        bump = category_page.bump
+    )]
+    pub category_page: Account<'info, CategoryIndexPage>,
+}
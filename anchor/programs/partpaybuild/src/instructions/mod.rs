@@ -3,9 +3,13 @@ pub mod vendor;
 pub mod equipment;
 pub mod contract;
 pub mod borrower;
+pub mod offer;
+pub mod config;
 
 pub use marketplace::*;
 pub use vendor::*;
 pub use equipment::*;
 pub use contract::*;
-pub use borrower::*;
\ No newline at end of file
+pub use borrower::*;
+pub use offer::*;
+pub use config::*;
\ No newline at end of file
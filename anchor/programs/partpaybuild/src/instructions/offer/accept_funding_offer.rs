@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{
+    errors::ErrorCode,
+    state::{
+        config::ProgramConfig,
+        delegate::VendorDelegate,
+        equipment::{Equipment, EquipmentStatus, FunderInfo},
+        escrow::Escrow,
+        marketplace::{FeeKind, Marketplace},
+        offer::FundingOffer,
+        vendor::Vendor,
+    },
+    utils::{
+        fees::marketplace_fee,
+        funding::{record_funding, validate_funding},
+        transfer::transfer_checked_received,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(quantity_to_fund: u64, borrower: Pubkey, unique_id: Pubkey)]
+pub struct AcceptFundingOffer<'info> {
+    #[account(
+        mut,
+        seeds = [FundingOffer::SEED_PREFIX, offer.funder.as_ref(), offer.offer_unique_id.as_ref()],
+        bump = offer.bump
+    )]
+    pub offer: Box<Account<'info, FundingOffer>>,
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program
+    )]
+    pub offer_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = acceptor,
+        space = Escrow::LEN,
+        seeds = [b"escrow", equipment.key().as_ref(), borrower.as_ref(), unique_id.as_ref()],
+        bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        init_if_needed,
+        payer = acceptor,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = equipment.mint == offer.mint @ ErrorCode::InvalidMint
+    )]
+    pub equipment: Box<Account<'info, Equipment>>,
+    #[account(
+        constraint = vendor.key() == equipment.vendor @ ErrorCode::InvalidVendor,
+        constraint = vendor.is_active() @ ErrorCode::VendorNotActive
+    )]
+    pub vendor: Box<Account<'info, Vendor>>,
+    #[account(mut)]
+    pub acceptor: Signer<'info>,
+    pub vendor_delegate: Option<Account<'info, VendorDelegate>>,
+    #[account(address = offer.mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub marketplace: Option<Box<Account<'info, Marketplace>>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_FUNDING) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Accepted by the vendor (or a finance delegate) on behalf of `borrower`, or
+/// by the borrower directly.
+pub fn accept_funding_offer(
+    ctx: Context<AcceptFundingOffer>,
+    quantity_to_fund: u64,
+    borrower: Pubkey,
+    _unique_id: Pubkey,
+    variant: u8,
+) -> Result<()> {
+    let acceptor = ctx.accounts.acceptor.key();
+    let vendor = &ctx.accounts.vendor;
+    require!(
+        acceptor == borrower
+            || vendor.authorizes(
+                &vendor.key(),
+                &acceptor,
+                ctx.accounts.vendor_delegate.as_deref(),
+                VendorDelegate::FINANCE
+            ),
+        ErrorCode::Unauthorized
+    );
+
+    let offer = &ctx.accounts.offer;
+    let equipment = &ctx.accounts.equipment;
+    require!(!offer.is_expired(Clock::get()?.unix_timestamp), ErrorCode::OfferExpired);
+    require!(
+        quantity_to_fund > 0 && quantity_to_fund <= offer.remaining_units(),
+        ErrorCode::InsufficientQuantity
+    );
+    require!(
+        offer.vendor.is_none_or(|vendor| vendor == equipment.vendor),
+        ErrorCode::OfferNotApplicable
+    );
+    require!(
+        offer.equipment.is_none_or(|key| key == equipment.key()),
+        ErrorCode::OfferNotApplicable
+    );
+    require!(
+        offer.category.is_none_or(|category| equipment.category == Some(category)),
+        ErrorCode::OfferNotApplicable
+    );
+    let unit_price = equipment.variant(variant)?.price;
+    require!(unit_price <= offer.max_unit_price, ErrorCode::OfferPriceExceeded);
+
+    let total_payment = validate_funding(equipment, variant, quantity_to_fund)?;
+    let fee = marketplace_fee(
+        &ctx.accounts.vendor,
+        ctx.accounts.marketplace.as_deref(),
+        ctx.accounts.treasury_token_account.as_deref(),
+        &ctx.accounts.usdc_mint.key(),
+        FeeKind::Funding,
+        total_payment,
+    )?;
+    let escrow_amount = total_payment - fee;
+
+    let funder = offer.funder;
+    let offer_unique_id = offer.offer_unique_id;
+    let offer_seeds: &[&[u8]] = &[
+        FundingOffer::SEED_PREFIX,
+        funder.as_ref(),
+        offer_unique_id.as_ref(),
+        &[offer.bump],
+    ];
+    let received_amount = transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.offer_vault.to_account_info(),
+        &mut ctx.accounts.escrow_token_account,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.offer.to_account_info(),
+        &[offer_seeds],
+        escrow_amount,
+    )?;
+    if fee > 0 {
+        let treasury = ctx.accounts.treasury_token_account.as_mut().ok_or(ErrorCode::InvalidTreasury)?;
+        transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.offer_vault.to_account_info(),
+            treasury,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.offer.to_account_info(),
+            &[offer_seeds],
+            fee,
+        )?;
+    }
+
+    let funder_info = FunderInfo {
+        funder,
+        quantity: quantity_to_fund,
+        minimum_deposit: ctx.accounts.offer.minimum_deposit,
+        duration_seconds: ctx.accounts.offer.duration_seconds,
+        borrower: Some(borrower),
+        escrow: ctx.accounts.escrow.key(),
+        criteria: ctx.accounts.offer.criteria.clone(),
+        funder_return: ctx.accounts.offer.funder_return.clone(),
+        amount_funded: total_payment,
+        units_sold: 0,
+        sold_principal: 0,
+        sold_repayable: 0,
+        amount_collected: 0,
+        unit_price,
+        terms_version: ctx.accounts.equipment.terms_version,
+        variant,
+    };
+    let equipment_key = ctx.accounts.equipment.key();
+    record_funding(
+        &mut ctx.accounts.equipment,
+        equipment_key,
+        &mut ctx.accounts.escrow,
+        received_amount,
+        funder_info,
+        EquipmentStatus::Reserved,
+    )?;
+    ctx.accounts.offer.units_accepted += quantity_to_fund;
+
+    msg!("Funding offer accepted for {} units", quantity_to_fund);
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, HarvestWithheldTokensToMint, Mint, TokenAccount, TokenInterface,
+};
+use crate::{
+    errors::ErrorCode,
+    state::{config::ProgramConfig, offer::FundingOffer},
+    utils::transfer::{has_transfer_fee, transfer_checked_received},
+};
+
+#[derive(Accounts)]
+pub struct CloseFundingOffer<'info> {
+    #[account(
+        mut,
+        close = funder,
+        has_one = funder,
+        seeds = [FundingOffer::SEED_PREFIX, funder.key().as_ref(), offer.offer_unique_id.as_ref()],
+        bump = offer.bump
+    )]
+    pub offer: Box<Account<'info, FundingOffer>>,
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program
+    )]
+    pub offer_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = funder
+    )]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = offer.mint @ ErrorCode::InvalidMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_RELEASES) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn close_funding_offer(ctx: Context<CloseFundingOffer>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    require!(
+        offer.is_expired(Clock::get()?.unix_timestamp) || offer.remaining_units() == 0,
+        ErrorCode::OfferStillActive
+    );
+
+    let funder = offer.funder;
+    let offer_unique_id = offer.offer_unique_id;
+    let offer_seeds: &[&[u8]] = &[
+        FundingOffer::SEED_PREFIX,
+        funder.as_ref(),
+        offer_unique_id.as_ref(),
+        &[offer.bump],
+    ];
+
+    let remaining = ctx.accounts.offer_vault.amount;
+    if remaining > 0 {
+        transfer_checked_received(
+            &ctx.accounts.token_program,
+            ctx.accounts.offer_vault.to_account_info(),
+            &mut ctx.accounts.funder_token_account,
+            &ctx.accounts.usdc_mint,
+            ctx.accounts.offer.to_account_info(),
+            &[offer_seeds],
+            remaining,
+        )?;
+    }
+
+    // Token-2022 refuses to close an account still holding withheld transfer fees.
+    if has_transfer_fee(&ctx.accounts.usdc_mint) {
+        token_interface::harvest_withheld_tokens_to_mint(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                HarvestWithheldTokensToMint {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.usdc_mint.to_account_info(),
+                },
+            ),
+            vec![ctx.accounts.offer_vault.to_account_info()],
+        )?;
+    }
+
+    token_interface::close_account(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.offer_vault.to_account_info(),
+                destination: ctx.accounts.funder.to_account_info(),
+                authority: ctx.accounts.offer.to_account_info(),
+            },
+        ).with_signer(&[offer_seeds]),
+    )?;
+
+    msg!("Funding offer closed, refunded {}", remaining);
+    Ok(())
+}
@@ -0,0 +1,7 @@
+mod post_funding_offer;
+mod accept_funding_offer;
+mod close_funding_offer;
+
+pub use post_funding_offer::*;
+pub use accept_funding_offer::*;
+pub use close_funding_offer::*;
\ No newline at end of file
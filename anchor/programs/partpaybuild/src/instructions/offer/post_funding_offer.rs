@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{
+    errors::ErrorCode,
+    state::{
+        config::ProgramConfig,
+        equipment::{FunderCriteria, FunderReturn},
+        offer::FundingOffer,
+    },
+    utils::{
+        transfer::{gross_up_for_transfer_fee, transfer_checked_received},
+        validation::{validate_duration, validate_price},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(offer_unique_id: Pubkey)]
+pub struct PostFundingOffer<'info> {
+    #[account(
+        init,
+        payer = funder,
+        space = FundingOffer::LEN,
+        seeds = [FundingOffer::SEED_PREFIX, funder.key().as_ref(), offer_unique_id.as_ref()],
+        bump
+    )]
+    pub offer: Box<Account<'info, FundingOffer>>,
+    #[account(
+        init_if_needed,
+        payer = funder,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program
+    )]
+    pub offer_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    #[account(constraint = config.is_mint_allowed(&usdc_mint.key()) @ ErrorCode::MintNotAllowed)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = funder
+    )]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        seeds = [ProgramConfig::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_FUNDING) @ ErrorCode::OperationPaused
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn post_funding_offer(
+    ctx: Context<PostFundingOffer>,
+    offer_unique_id: Pubkey,
+    vendor: Option<Pubkey>,
+    equipment: Option<Pubkey>,
+    category: Option<Pubkey>,
+    max_units: u64,
+    max_unit_price: u64,
+    minimum_deposit: u64,
+    duration_seconds: i64,
+    criteria: FunderCriteria,
+    funder_return: FunderReturn,
+    expires_at: i64,
+) -> Result<()> {
+    require!(max_units > 0, ErrorCode::InvalidAmount);
+    validate_price(max_unit_price)?;
+    validate_duration(duration_seconds)?;
+    require!(expires_at > Clock::get()?.unix_timestamp, ErrorCode::InvalidTimestamp);
+
+    let total_commitment = max_unit_price.checked_mul(max_units).ok_or(ErrorCode::MathOverflow)?;
+    // The vault must hold the full commitment, so cover any transfer fee up front.
+    let gross_commitment = gross_up_for_transfer_fee(&ctx.accounts.usdc_mint, total_commitment)?;
+    let received = transfer_checked_received(
+        &ctx.accounts.token_program,
+        ctx.accounts.funder_token_account.to_account_info(),
+        &mut ctx.accounts.offer_vault,
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.funder.to_account_info(),
+        &[],
+        gross_commitment,
+    )?;
+    require!(received >= total_commitment, ErrorCode::InsufficientFunds);
+
+    let offer = &mut ctx.accounts.offer;
+    offer.funder = ctx.accounts.funder.key();
+    offer.offer_unique_id = offer_unique_id;
+    offer.vendor = vendor;
+    offer.equipment = equipment;
+    offer.category = category;
+    offer.mint = ctx.accounts.usdc_mint.key();
+    offer.max_units = max_units;
+    offer.units_accepted = 0;
+    offer.max_unit_price = max_unit_price;
+    offer.minimum_deposit = minimum_deposit;
+    offer.duration_seconds = duration_seconds;
+    offer.criteria = criteria;
+    offer.funder_return = funder_return;
+    offer.expires_at = expires_at;
+    offer.bump = ctx.bumps.offer;
+
+    msg!("Funding offer posted for up to {} units", max_units);
+    Ok(())
+}
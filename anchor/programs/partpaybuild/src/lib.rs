@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 use state::{
+    marketplace::{MarketplaceEquipmentResponse, MarketplaceFees, MarketplaceRules},
+    category::CategoryEquipmentResponse,
+    cart::CartLineRequest,
     vendor::VendorEquipmentResponse,
     contract::{ContractStatus, InstallmentFrequency},
-    equipment::{Equipment, PaymentPreference},
-    funded::FunderEquipmentResponse
+    equipment::{Equipment, FunderCriteria, FunderReturn, PaymentPreference},
+    funded::{FunderEquipmentResponse, FunderPortfolioResponse},
 };
 
 declare_id!("PARnAABYT9Kuq3sgokcr4Tyz6FY7DUKpiy2Rjrp3jSh");
@@ -20,6 +23,57 @@ use instructions::*;
 pub mod partpay {
     use super::*;
     
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        msg!("Starting initialize_config");
+        config::initialize_config(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: u8) -> Result<()> {
+        msg!("Starting set_paused");
+        config::set_paused(ctx, paused)
+    }
+
+    pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        msg!("Starting propose_admin");
+        config::propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        msg!("Starting accept_admin");
+        config::accept_admin(ctx)
+    }
+
+    pub fn add_allowed_mint(ctx: Context<UpdateConfig>, mint: Pubkey) -> Result<()> {
+        msg!("Starting add_allowed_mint");
+        config::add_allowed_mint(ctx, mint)
+    }
+
+    pub fn remove_allowed_mint(ctx: Context<UpdateConfig>, mint: Pubkey) -> Result<()> {
+        msg!("Starting remove_allowed_mint");
+        config::remove_allowed_mint(ctx, mint)
+    }
+
+    pub fn set_currency_feed(
+        ctx: Context<SetCurrencyFeed>,
+        currency: [u8; 3],
+        mint: Pubkey,
+        decimals: u8,
+        price_feed: Pubkey,
+        max_staleness_seconds: i64,
+        max_confidence_bps: u16,
+    ) -> Result<()> {
+        msg!("Starting set_currency_feed");
+        config::set_currency_feed(
+            ctx,
+            currency,
+            mint,
+            decimals,
+            price_feed,
+            max_staleness_seconds,
+            max_confidence_bps,
+        )
+    }
+
     pub fn create_marketplace(
         ctx: Context<CreateMarketplace>,
         name: String,
@@ -29,6 +83,70 @@ pub mod partpay {
         marketplace::create_marketplace(ctx, name, uri)
     }
 
+    pub fn apply_to_marketplace(ctx: Context<ApplyToMarketplace>) -> Result<()> {
+        msg!("Starting apply_to_marketplace");
+        marketplace::apply_to_marketplace(ctx)
+    }
+
+    pub fn approve_vendor(ctx: Context<ApproveVendor>) -> Result<()> {
+        msg!("Starting approve_vendor");
+        marketplace::approve_vendor(ctx)
+    }
+
+    pub fn reject_vendor(ctx: Context<ReviewVendorApplication>) -> Result<()> {
+        msg!("Starting reject_vendor");
+        marketplace::reject_vendor(ctx)
+    }
+
+    pub fn update_marketplace_rules(
+        ctx: Context<UpdateMarketplaceRules>,
+        rules: MarketplaceRules,
+    ) -> Result<()> {
+        msg!("Starting update_marketplace_rules");
+        marketplace::update_marketplace_rules(ctx, rules)
+    }
+
+    pub fn update_marketplace_fees(
+        ctx: Context<UpdateMarketplaceFees>,
+        fees: MarketplaceFees,
+    ) -> Result<()> {
+        msg!("Starting update_marketplace_fees");
+        marketplace::update_marketplace_fees(ctx, fees)
+    }
+
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        msg!("Starting initialize_treasury");
+        marketplace::initialize_treasury(ctx)
+    }
+
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        msg!("Starting withdraw_fees");
+        marketplace::withdraw_fees(ctx, amount)
+    }
+
+    pub fn get_marketplace_equipment<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetMarketplaceEquipment<'info>>
+    ) -> Result<MarketplaceEquipmentResponse> {
+        marketplace::get_marketplace_equipment(ctx)
+    }
+
+    pub fn create_equipment_category(
+        ctx: Context<CreateEquipmentCategory>,
+        slug: String,
+        name: String,
+    ) -> Result<()> {
+        msg!("Starting create_equipment_category");
+        marketplace::create_equipment_category(ctx, slug, name)
+    }
+
+    pub fn get_equipment_by_category<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetEquipmentByCategory<'info>>,
+        page: u32,
+        tag: Option<String>,
+    ) -> Result<CategoryEquipmentResponse> {
+        marketplace::get_equipment_by_category(ctx, page, tag)
+    }
+
     pub fn create_vendor(
         ctx: Context<CreateVendor>,
         name: String,
@@ -40,6 +158,70 @@ pub mod partpay {
         vendor::create_vendor(ctx, name, uri, unique_id, collection_unique_id)
     }
 
+    pub fn update_vendor(
+        ctx: Context<UpdateVendor>,
+        name: Option<String>,
+        uri: Option<String>,
+    ) -> Result<()> {
+        msg!("Starting update_vendor");
+        vendor::update_vendor(ctx, name, uri)
+    }
+
+    pub fn transfer_vendor_authority(
+        ctx: Context<TransferVendorAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        msg!("Starting transfer_vendor_authority");
+        vendor::transfer_vendor_authority(ctx, new_authority)
+    }
+
+    pub fn recover_vendor_authority(
+        ctx: Context<RecoverVendorAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        msg!("Starting recover_vendor_authority");
+        vendor::recover_vendor_authority(ctx, new_authority)
+    }
+
+    pub fn accept_vendor_authority(ctx: Context<AcceptVendorAuthority>) -> Result<()> {
+        msg!("Starting accept_vendor_authority");
+        vendor::accept_vendor_authority(ctx)
+    }
+
+    pub fn set_vendor_delegate(
+        ctx: Context<SetVendorDelegate>,
+        delegate: Pubkey,
+        permissions: u8,
+    ) -> Result<()> {
+        msg!("Starting set_vendor_delegate");
+        vendor::set_vendor_delegate(ctx, delegate, permissions)
+    }
+
+    pub fn remove_vendor_delegate(ctx: Context<RemoveVendorDelegate>) -> Result<()> {
+        msg!("Starting remove_vendor_delegate");
+        vendor::remove_vendor_delegate(ctx)
+    }
+
+    pub fn withdraw_vendor_payouts(ctx: Context<WithdrawVendorPayouts>, amount: u64) -> Result<()> {
+        msg!("Starting withdraw_vendor_payouts");
+        vendor::withdraw_vendor_payouts(ctx, amount)
+    }
+
+    pub fn suspend_vendor(ctx: Context<UpdateVendorStatus>) -> Result<()> {
+        msg!("Starting suspend_vendor");
+        vendor::suspend_vendor(ctx)
+    }
+
+    pub fn reinstate_vendor(ctx: Context<UpdateVendorStatus>) -> Result<()> {
+        msg!("Starting reinstate_vendor");
+        vendor::reinstate_vendor(ctx)
+    }
+
+    pub fn deactivate_vendor(ctx: Context<UpdateVendorStatus>) -> Result<()> {
+        msg!("Starting deactivate_vendor");
+        vendor::deactivate_vendor(ctx)
+    }
+
     pub fn upload_equipment(
         ctx: Context<UploadEquipment>,
         name: String,
@@ -50,6 +232,8 @@ pub mod partpay {
         minimum_deposit: u64,
         max_duration_seconds: i64,
         payment_preference: PaymentPreference,
+        reference_currency: Option<[u8; 3]>,
+        tags: Vec<String>,
     ) -> Result<()> {
         equipment::upload_equipment(
             ctx,
@@ -61,6 +245,8 @@ pub mod partpay {
             minimum_deposit,
             max_duration_seconds,
             payment_preference,
+            reference_currency,
+            tags,
         )
     }
 
@@ -74,10 +260,62 @@ pub mod partpay {
         equipment::update_equipment(ctx, name, uri, price)
     }
 
+    pub fn restock_equipment(ctx: Context<AdjustStock>, variant: u8, quantity: u64) -> Result<()> {
+        msg!("Starting restock_equipment");
+        equipment::restock_equipment(ctx, variant, quantity)
+    }
+
+    pub fn reduce_stock(ctx: Context<AdjustStock>, variant: u8, quantity: u64) -> Result<()> {
+        msg!("Starting reduce_stock");
+        equipment::reduce_stock(ctx, variant, quantity)
+    }
+
+    pub fn add_equipment_variant(
+        ctx: Context<ManageEquipmentVariant>,
+        name: String,
+        price: u64,
+        minimum_deposit: u64,
+        quantity: u64,
+    ) -> Result<()> {
+        msg!("Starting add_equipment_variant");
+        equipment::add_equipment_variant(ctx, name, price, minimum_deposit, quantity)
+    }
+
+    pub fn update_equipment_variant(
+        ctx: Context<ManageEquipmentVariant>,
+        variant: u8,
+        name: Option<String>,
+        price: Option<u64>,
+        minimum_deposit: Option<u64>,
+    ) -> Result<()> {
+        msg!("Starting update_equipment_variant");
+        equipment::update_equipment_variant(ctx, variant, name, price, minimum_deposit)
+    }
+
+    pub fn reprice_funder_position(
+        ctx: Context<RepriceFunderPosition>,
+        escrow_owner: Pubkey,
+        unique_id: Pubkey,
+    ) -> Result<()> {
+        msg!("Starting reprice_funder_position");
+        equipment::reprice_funder_position(ctx, escrow_owner, unique_id)
+    }
+
+    pub fn delist_equipment(ctx: Context<DelistEquipment>) -> Result<()> {
+        msg!("Starting delist_equipment");
+        equipment::delist_equipment(ctx)
+    }
+
+    pub fn close_equipment(ctx: Context<CloseEquipment>) -> Result<()> {
+        msg!("Starting close_equipment");
+        equipment::close_equipment(ctx)
+    }
+
     pub fn get_all_vendor_equipment<'info>(
-        ctx: Context<'_, '_, 'info, 'info, GetAllVendorEquipment<'info>> 
+        ctx: Context<'_, '_, 'info, 'info, GetAllVendorEquipment<'info>>,
+        page: u32,
     ) -> Result<VendorEquipmentResponse> {
-        equipment::get_all_vendor_equipment(ctx)
+        equipment::get_all_vendor_equipment(ctx, page)
     }
 
     pub fn get_equipment(ctx: Context<GetEquipment>) -> Result<Equipment> {
@@ -107,7 +345,9 @@ pub mod partpay {
         installment_frequency: InstallmentFrequency,
         deposit: u64,
         insurance_premium: Option<u64>,
-        funder_unique_id: Option<Pubkey>
+        funder_unique_id: Option<Pubkey>,
+        variant: u8,
+        quantity: u64,
     ) -> Result<()> {
         contract::create_contract(
             ctx,
@@ -116,10 +356,47 @@ pub mod partpay {
             installment_frequency,
             deposit,
             insurance_premium,
-            funder_unique_id
+            funder_unique_id,
+            variant,
+            quantity
         )
     }
 
+    pub fn create_cart_contract<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateCartContract<'info>>,
+        cart_unique_id: Pubkey,
+        lines: Vec<CartLineRequest>,
+        total_amount: u64,
+        deposit: u64,
+        installment_frequency: InstallmentFrequency,
+    ) -> Result<()> {
+        msg!("Starting create_cart_contract");
+        contract::create_cart_contract(ctx, cart_unique_id, lines, total_amount, deposit, installment_frequency)
+    }
+
+    pub fn make_cart_payment<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MakeCartPayment<'info>>,
+        payment_amount: u64,
+    ) -> Result<()> {
+        msg!("Starting make_cart_payment");
+        contract::make_cart_payment(ctx, payment_amount)
+    }
+
+    pub fn confirm_cart_delivery(ctx: Context<ConfirmCartDelivery>, line_index: u8) -> Result<()> {
+        msg!("Starting confirm_cart_delivery");
+        contract::confirm_cart_delivery(ctx, line_index)
+    }
+
+    pub fn mark_cart_line_shipped(ctx: Context<MarkCartLineShipped>, line_index: u8) -> Result<()> {
+        msg!("Starting mark_cart_line_shipped");
+        contract::mark_cart_line_shipped(ctx, line_index)
+    }
+
+    pub fn cancel_cart_line(ctx: Context<CancelCartLine>, line_index: u8) -> Result<()> {
+        msg!("Starting cancel_cart_line");
+        contract::cancel_cart_line(ctx, line_index)
+    }
+
     pub fn get_contract_status(ctx: Context<GetContractStatus>) -> Result<ContractStatus> {
         msg!("Starting get_contract_status");
         contract::get_contract_status(ctx)
@@ -132,43 +409,39 @@ pub mod partpay {
         contract::make_payment(ctx, payment_amount)
     }
 
-    pub fn track_repayment(
-        ctx: Context<TrackRepayment>,
-        amount: u64,
-        on_time_score: f64,
-        contract_unique_id: Pubkey,
-    ) -> Result<()> {
-        borrower::track_repayment(
-            ctx,
-            amount,
-            on_time_score,
-            contract_unique_id,
-        )
-    }
-
     pub fn get_funder_equipment<'info>(
         ctx: Context<'_, '_, 'info, 'info, GetFunderEquipment<'info>>
     ) -> Result<FunderEquipmentResponse> {
         equipment::get_funded_equipment(ctx)
     }
 
+    pub fn get_funder_portfolio<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetFunderPortfolio<'info>>
+    ) -> Result<FunderPortfolioResponse> {
+        equipment::get_funder_portfolio(ctx)
+    }
+
     pub fn fund_equipment_for_listing(
         ctx: Context<FundEquipmentForListing>,
         quantity_to_fund: u64,
         minimum_deposit: u64,
         duration_seconds: i64,
         unique_id: Pubkey,
+        criteria: FunderCriteria,
+        funder_return: FunderReturn,
+        variant: u8,
     ) -> Result<()> {
-        equipment::fund_equipment_for_listing(ctx, quantity_to_fund, minimum_deposit, duration_seconds, unique_id)
+        equipment::fund_equipment_for_listing(ctx, quantity_to_fund, minimum_deposit, duration_seconds, unique_id, criteria, funder_return, variant)
     }
 
     pub fn fund_equipment_for_borrower_no_payment(
         ctx: Context<FundEquipmentForBorrowerNoPayment>,
         quantity_to_fund: u64,
         borrower: Pubkey,
-        unique_id: Pubkey
+        unique_id: Pubkey,
+        variant: u8,
     ) -> Result<()> {
-        equipment::fund_equipment_for_borrower_no_payment(ctx, quantity_to_fund, borrower, unique_id)
+        equipment::fund_equipment_for_borrower_no_payment(ctx, quantity_to_fund, borrower, unique_id, variant)
     }
 
     pub fn fund_equipment_for_borrower_with_payment(
@@ -177,16 +450,100 @@ pub mod partpay {
         borrower: Pubkey,
         minimum_deposit: u64,
         duration_seconds: i64,
-        unique_id: Pubkey
+        unique_id: Pubkey,
+        criteria: FunderCriteria,
+        funder_return: FunderReturn,
+        variant: u8,
     ) -> Result<()> {
-        equipment::fund_equipment_for_borrower_with_payment(ctx, quantity_to_fund, borrower, minimum_deposit, duration_seconds, unique_id)
+        equipment::fund_equipment_for_borrower_with_payment(ctx, quantity_to_fund, borrower, minimum_deposit, duration_seconds, unique_id, criteria, funder_return, variant)
     }
 
-    pub fn confirm_delivery(
-        ctx: Context<ConfirmDelivery>,
-        unique_id: Pubkey
+    pub fn post_funding_offer(
+        ctx: Context<PostFundingOffer>,
+        offer_unique_id: Pubkey,
+        vendor: Option<Pubkey>,
+        equipment: Option<Pubkey>,
+        category: Option<Pubkey>,
+        max_units: u64,
+        max_unit_price: u64,
+        minimum_deposit: u64,
+        duration_seconds: i64,
+        criteria: FunderCriteria,
+        funder_return: FunderReturn,
+        expires_at: i64,
+    ) -> Result<()> {
+        msg!("Starting post_funding_offer");
+        offer::post_funding_offer(
+            ctx,
+            offer_unique_id,
+            vendor,
+            equipment,
+            category,
+            max_units,
+            max_unit_price,
+            minimum_deposit,
+            duration_seconds,
+            criteria,
+            funder_return,
+            expires_at,
+        )
+    }
+
+    pub fn accept_funding_offer(
+        ctx: Context<AcceptFundingOffer>,
+        quantity_to_fund: u64,
+        borrower: Pubkey,
+        unique_id: Pubkey,
+        variant: u8,
+    ) -> Result<()> {
+        msg!("Starting accept_funding_offer");
+        offer::accept_funding_offer(ctx, quantity_to_fund, borrower, unique_id, variant)
+    }
+
+    pub fn close_funding_offer(ctx: Context<CloseFundingOffer>) -> Result<()> {
+        msg!("Starting close_funding_offer");
+        offer::close_funding_offer(ctx)
+    }
+
+    pub fn purchase_equipment(
+        ctx: Context<PurchaseEquipment>,
+        order_unique_id: Pubkey,
+        variant: u8,
+        quantity: u64,
+    ) -> Result<()> {
+        msg!("Starting purchase_equipment");
+        equipment::purchase_equipment(ctx, order_unique_id, variant, quantity)
+    }
+
+    pub fn mark_shipped(ctx: Context<MarkShipped>) -> Result<()> {
+        msg!("Starting mark_shipped");
+        equipment::mark_shipped(ctx)
+    }
+
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        msg!("Starting cancel_order");
+        equipment::cancel_order(ctx)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+        msg!("Starting open_dispute");
+        equipment::open_dispute(ctx)
+    }
+
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+        refund_borrower: bool,
     ) -> Result<()> {
-        equipment::confirm_delivery(ctx, unique_id)
+        msg!("Starting resolve_dispute");
+        equipment::resolve_dispute(ctx, refund_borrower)
+    }
+
+    pub fn confirm_delivery<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfirmDelivery<'info>>,
+        unique_id: Pubkey,
+        units: u64
+    ) -> Result<()> {
+        equipment::confirm_delivery(ctx, unique_id, units)
     }
 
     pub fn confirm_funded_delivery(
@@ -196,6 +553,20 @@ pub mod partpay {
         equipment::confirm_funded_delivery(ctx, unique_id)
     }
 
+    pub fn issue_kyc_attestation(
+        ctx: Context<IssueKycAttestation>,
+        subject: Pubkey,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        msg!("Starting issue_kyc_attestation");
+        borrower::issue_kyc_attestation(ctx, subject, expires_at)
+    }
+
+    pub fn revoke_kyc_attestation(ctx: Context<RevokeKycAttestation>) -> Result<()> {
+        msg!("Starting revoke_kyc_attestation");
+        borrower::revoke_kyc_attestation(ctx)
+    }
+
     pub fn view_credit_score(ctx: Context<ViewCreditScore>) -> Result<u64> {
         let credit_score = ctx.accounts.credit_score.score;
         Ok(credit_score)
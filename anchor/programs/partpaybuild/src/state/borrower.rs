@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
 
 #[account]
 pub struct Borrower {
@@ -13,4 +14,44 @@ pub struct Borrower {
 
 impl Borrower {
     pub const SEED_PREFIX: &'static [u8] = b"borrower";
+
+    /// Reads the profile at a buyer's borrower PDA, if it has been initialized.
+    pub fn load(info: &AccountInfo, program_id: &Pubkey) -> Result<Option<Borrower>> {
+        if info.owner != program_id || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(Some(Borrower::try_deserialize(&mut &data[..])?))
+    }
+
+    pub fn store(&self, info: &AccountInfo) -> Result<()> {
+        let mut data = info.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+
+    /// Counts a new loan against the profile at `info`, if there is one.
+    /// Returns whether it was counted.
+    pub fn open_loan(info: &AccountInfo, program_id: &Pubkey) -> Result<bool> {
+        match Self::load(info, program_id)? {
+            Some(mut profile) => {
+                profile.total_loans = profile.total_loans.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+                profile.store(info)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Releases a loan counted by `open_loan` once it is repaid or cancelled.
+    pub fn close_loan(info: &AccountInfo, program_id: &Pubkey, counted: &mut bool) -> Result<()> {
+        if !*counted {
+            return Ok(());
+        }
+        if let Some(mut profile) = Self::load(info, program_id)? {
+            profile.total_loans = profile.total_loans.saturating_sub(1);
+            profile.store(info)?;
+        }
+        *counted = false;
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::{contract::InstallmentFrequency, equipment::DeliveryStatus};
+
+/// A single BNPL contract over several listings of one vendor. The deposit
+/// is escrowed in the cart's vault and released line by line on delivery;
+/// installments go straight to the vendor.
+#[account]
+pub struct CartContract {
+    pub borrower: Pubkey,
+    pub vendor: Pubkey,
+    pub cart_unique_id: Pubkey,
+    pub stablecoin_mint: Pubkey,
+    pub lines: Vec<CartLine>,
+    pub total_amount: u64,
+    pub amount_paid: u64,
+    pub deposit: u64,
+    pub start_date: i64,
+    pub end_date: i64,
+    pub last_payment_date: i64,
+    pub installment_count: u8,
+    pub paid_installments: u8,
+    pub installment_frequency: InstallmentFrequency,
+    pub is_completed: bool,
+    /// Whether the cart is counted in its borrower profile's `total_loans`.
+    pub counts_toward_loans: bool,
+    pub bump: u8,
+}
+
+/// One equipment/quantity line of a cart. `escrow_amount` is the line's share
+/// of the escrowed deposit; the line covers `quantity` consecutive units from
+/// `equipment_unit_index`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CartLine {
+    pub equipment: Pubkey,
+    pub variant: u8,
+    pub quantity: u64,
+    pub equipment_unit_index: u64,
+    pub unit_price: u64,
+    pub escrow_amount: u64,
+    pub delivery_status: DeliveryStatus,
+}
+
+impl CartLine {
+    pub const LEN: usize = 32 + 1 + 8 + 8 + 8 + 8 + 1;
+}
+
+/// A line as requested at checkout; the listing is the matching remaining
+/// account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CartLineRequest {
+    pub variant: u8,
+    pub quantity: u64,
+}
+
+impl CartContract {
+    pub const SEED_PREFIX: &'static [u8] = b"cart_contract";
+    pub const MAX_LINES: usize = 6;
+    pub const LEN: usize = 8 + // Discriminator
+        32 + // borrower
+        32 + // vendor
+        32 + // cart_unique_id
+        32 + // stablecoin_mint
+        (4 + CartLine::LEN * Self::MAX_LINES) + // lines
+        8 +  // total_amount
+        8 +  // amount_paid
+        8 +  // deposit
+        8 +  // start_date
+        8 +  // end_date
+        8 +  // last_payment_date
+        1 +  // installment_count
+        1 +  // paid_installments
+        9 +  // installment_frequency (1 tag + 8 payload)
+        1 +  // is_completed
+        1 +  // counts_toward_loans
+        1;   // bump
+}
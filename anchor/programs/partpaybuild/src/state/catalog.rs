@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// One page of a vendor's listing index. Listings are appended in upload
+/// order; slot `n` lives at index `n % ENTRIES_PER_PAGE` of page
+/// `n / ENTRIES_PER_PAGE`.
+#[account]
+pub struct VendorCatalogPage {
+    pub vendor: Pubkey,
+    pub page: u32,
+    pub entries: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl VendorCatalogPage {
+    pub const SEED_PREFIX: &'static [u8] = b"vendor_catalog_page";
+    pub const ENTRIES_PER_PAGE: u64 = 64;
+    pub const LEN: usize = 8 + 32 + 4 + (4 + 32 * Self::ENTRIES_PER_PAGE as usize) + 1;
+
+    pub fn page_for_slot(slot: u64) -> u32 {
+        (slot / Self::ENTRIES_PER_PAGE) as u32
+    }
+
+    pub fn first_slot(&self) -> u64 {
+        self.page as u64 * Self::ENTRIES_PER_PAGE
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::equipment::{EquipmentStatus, PaymentPreference};
+
+/// A discovery section defined by a marketplace, e.g. "irrigation" or
+/// "solar". Listings of the marketplace's vendors opt in at upload.
+#[account]
+pub struct EquipmentCategory {
+    pub marketplace: Pubkey,
+    pub slug: String,
+    pub name: String,
+    /// Listings currently in the category.
+    pub listing_count: u64,
+    /// Number of slots handed out in the `CategoryIndexPage` index.
+    pub slots: u64,
+    pub bump: u8,
+}
+
+impl EquipmentCategory {
+    pub const SEED_PREFIX: &'static [u8] = b"equipment_category";
+    pub const MAX_SLUG_LEN: usize = 32;
+    pub const LEN: usize = 8 + 32 + (4 + Self::MAX_SLUG_LEN) + (4 + 32) + 8 + 8 + 1;
+}
+
+/// One page of a category's listing index, laid out like
+/// `VendorCatalogPage`.
+#[account]
+pub struct CategoryIndexPage {
+    pub category: Pubkey,
+    pub page: u32,
+    pub entries: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl CategoryIndexPage {
+    pub const SEED_PREFIX: &'static [u8] = b"category_index_page";
+    pub const ENTRIES_PER_PAGE: u64 = 64;
+    pub const LEN: usize = 8 + 32 + 4 + (4 + 32 * Self::ENTRIES_PER_PAGE as usize) + 1;
+
+    pub fn page_for_slot(slot: u64) -> u32 {
+        (slot / Self::ENTRIES_PER_PAGE) as u32
+    }
+
+    pub fn first_slot(&self) -> u64 {
+        self.page as u64 * Self::ENTRIES_PER_PAGE
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CategoryEquipmentInfo {
+    pub equipment_pda: Pubkey,
+    pub vendor: Pubkey,
+    pub name: String,
+    pub uri: String,
+    pub asset: Pubkey,
+    pub tags: Vec<String>,
+    pub status: EquipmentStatus,
+    pub available_for_bnpl: u64,
+    pub available_for_funding: u64,
+    pub min_price: u64,
+    pub max_price: u64,
+    pub minimum_deposit: u64,
+    pub max_duration_seconds: i64,
+    pub payment_preference: PaymentPreference,
+    pub reference_currency: Option<[u8; 3]>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CategoryEquipmentResponse {
+    pub listing_count: u64,
+    pub equipment: Vec<CategoryEquipmentInfo>,
+    pub failed_loads: Vec<u64>,
+}
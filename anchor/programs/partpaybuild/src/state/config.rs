@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub paused: u8,
+    pub allowed_mints: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl ProgramConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"program_config";
+    pub const MAX_ALLOWED_MINTS: usize = 8;
+    pub const LEN: usize = 8 + 32 + 33 + 1 + (4 + 32 * Self::MAX_ALLOWED_MINTS) + 1;
+
+    /// Every state-changing instruction checks one of these flags, except the
+    /// config, vendor status, authority recovery and KYC revocation paths,
+    /// which stay open so an incident can be handled while paused.
+    pub const PAUSE_LISTINGS: u8 = 1 << 0;
+    pub const PAUSE_FUNDING: u8 = 1 << 1;
+    pub const PAUSE_CONTRACTS: u8 = 1 << 2;
+    pub const PAUSE_PAYMENTS: u8 = 1 << 3;
+    pub const PAUSE_RELEASES: u8 = 1 << 4;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_LISTINGS
+        | Self::PAUSE_FUNDING
+        | Self::PAUSE_CONTRACTS
+        | Self::PAUSE_PAYMENTS
+        | Self::PAUSE_RELEASES;
+
+    pub fn is_paused(&self, operation: u8) -> bool {
+        self.paused & operation != 0
+    }
+
+    pub fn is_mint_allowed(&self, mint: &Pubkey) -> bool {
+        self.allowed_mints.contains(mint)
+    }
+}
@@ -1,10 +1,16 @@
 use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::{currency::FixedRate, equipment::DeliveryStatus},
+};
 
 #[account]
 pub struct BNPLContract {
     pub borrower: Pubkey,
     pub payee: Pubkey,
     pub equipment: Pubkey,
+    /// Index of the contract's first unit on the listing; the contract covers
+    /// `quantity` consecutive units from here.
     pub equipment_unit_index: u64,
     pub total_amount: u64,
     pub amount_paid: u64,
@@ -22,6 +28,25 @@ pub struct BNPLContract {
     pub credit_score_delta: i8,
     pub stablecoin_mint: Pubkey,
     pub escrow: Pubkey,
+    pub funder_escrow: Option<Pubkey>,
+    /// Set when the equipment is priced in a reference currency; amounts on
+    /// the contract are already converted at this rate.
+    pub fx_rate: Option<FixedRate>,
+    /// Unit price the contract was priced on, in the listing's pricing units,
+    /// and the listing terms version it came from.
+    pub unit_price: u64,
+    pub terms_version: u32,
+    pub variant: u8,
+    pub kind: ContractKind,
+    /// Delivery of this order's units; `Delivered` once every unit is.
+    pub delivery_status: DeliveryStatus,
+    /// Core asset minted for the order's first delivered unit. Each unit's
+    /// asset is a PDA of the contract and its offset within the order.
+    pub unit_asset: Option<Pubkey>,
+    pub quantity: u64,
+    pub delivered_quantity: u64,
+    /// Whether the contract is counted in its borrower profile's `total_loans`.
+    pub counts_toward_loans: bool,
 }
 
 impl BNPLContract {
@@ -45,7 +70,93 @@ impl BNPLContract {
         1 +  // is_insured
         1 +  // credit_score_delta
         32 + // stablecoin_mint
-        32; 
+        32 + // escrow
+        33 + // funder_escrow
+        1 + FixedRate::LEN + // fx_rate
+        8 +  // unit_price
+        4 +  // terms_version
+        1 +  // variant
+        1 +  // kind
+        1 +  // delivery_status
+        33 + // unit_asset
+        8 +  // quantity
+        8 +  // delivered_quantity
+        1;   // counts_toward_loans
+
+    pub fn status(&self, now: i64) -> Result<ContractStatus> {
+        let time_since_start = now
+            .checked_sub(self.start_date)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let total_duration = self
+            .end_date
+            .checked_sub(self.start_date)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let progress = ((time_since_start as f64 / total_duration as f64 * 100.0)
+            .min(100.0)) as u8;
+
+        let total_due = self.total_due()?;
+
+        let remaining_amount = total_due
+            .checked_sub(self.amount_paid)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let time_since_last_payment = now
+            .checked_sub(self.last_payment_date)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let next_payment_due = self
+            .last_payment_date
+            .checked_add(self.installment_frequency.as_seconds())
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let is_payment_overdue = now > next_payment_due;
+
+        Ok(ContractStatus {
+            progress,
+            total_due,
+            remaining_amount,
+            time_since_last_payment,
+            is_payment_overdue,
+            next_payment_due,
+            insurance_premium: self.insurance_premium,
+        })
+    }
+
+    pub fn undelivered_quantity(&self) -> u64 {
+        self.quantity.saturating_sub(self.delivered_quantity)
+    }
+
+    pub fn total_due(&self) -> Result<u64> {
+        if self.is_insured {
+            Ok(self
+                .total_amount
+                .checked_add(self.insurance_premium.unwrap_or(0))
+                .ok_or(ErrorCode::MathOverflow)?)
+        } else {
+            Ok(self.total_amount)
+        }
+    }
+
+    /// Amount the schedule expected by `now` that has not been paid yet,
+    /// assuming the balance after the deposit is spread evenly over the installments.
+    pub fn amount_overdue(&self, now: i64) -> Result<u64> {
+        if self.is_completed || self.installment_count == 0 {
+            return Ok(0);
+        }
+        let total_due = self.total_due()?;
+        let frequency = self.installment_frequency.as_seconds();
+        let elapsed_installments = if frequency > 0 {
+            (now.saturating_sub(self.start_date) / frequency).min(self.installment_count as i64) as u64
+        } else {
+            0
+        };
+        let scheduled = total_due.saturating_sub(self.deposit) as u128
+            * elapsed_installments as u128
+            / self.installment_count as u128;
+        let expected_paid = (self.deposit as u128 + scheduled).min(total_due as u128) as u64;
+        Ok(expected_paid.saturating_sub(self.amount_paid))
+    }
 }
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct ContractStatus {
@@ -58,6 +169,14 @@ pub struct ContractStatus {
     pub insurance_premium: Option<u64>,
 } 
 
+/// `Bnpl` is a financed sale with a deposit and installments; `Outright` is a
+/// sale paid in full up front, held in escrow until delivery.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ContractKind {
+    Bnpl,
+    Outright,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum InstallmentFrequency {
     Daily,
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Admin-approved price feed for one reference currency (ISO 4217 code such
+/// as `b"NGN"`), quoted against one settlement mint.
+#[account]
+pub struct CurrencyFeed {
+    pub currency: [u8; 3],
+    /// Settlement mint the feed quotes in; the feed only converts for it.
+    pub mint: Pubkey,
+    /// Minor-unit decimals reference prices are expressed in (2 for kobo).
+    pub decimals: u8,
+    pub price_feed: Pubkey,
+    pub max_staleness_seconds: i64,
+    pub max_confidence_bps: u16,
+    pub bump: u8,
+}
+
+impl CurrencyFeed {
+    pub const SEED_PREFIX: &'static [u8] = b"currency_feed";
+    pub const LEN: usize = 8 + 3 + 32 + 1 + 32 + 8 + 2 + 1;
+    pub const BPS_DENOMINATOR: u128 = 10_000;
+
+    /// Reads the feed and locks in its current rate, rejecting stale quotes and
+    /// quotes whose confidence interval is too wide.
+    pub fn read_rate(&self, price_feed: &AccountInfo, now: i64) -> Result<FixedRate> {
+        require_keys_eq!(price_feed.key(), self.price_feed, ErrorCode::InvalidPriceFeed);
+        let data = price_feed.try_borrow_data()?;
+        let quote = PriceFeedData::deserialize(&mut &data[..])
+            .map_err(|_| error!(ErrorCode::InvalidPriceFeed))?;
+
+        require!(quote.price > 0, ErrorCode::InvalidPriceFeed);
+        require!(
+            now.saturating_sub(quote.publish_time) <= self.max_staleness_seconds,
+            ErrorCode::PriceFeedStale
+        );
+        let price = quote.price as u64;
+        require!(
+            quote.conf as u128 * Self::BPS_DENOMINATOR
+                <= price as u128 * self.max_confidence_bps as u128,
+            ErrorCode::PriceFeedConfidence
+        );
+
+        Ok(FixedRate {
+            currency: self.currency,
+            currency_decimals: self.decimals,
+            price,
+            expo: quote.expo,
+            publish_time: quote.publish_time,
+        })
+    }
+}
+
+/// Layout of the external price-feed account, read from offset 0: the value
+/// of one whole unit of the reference currency in the settlement stablecoin,
+/// as `price * 10^expo`, with `conf` on the same scale.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PriceFeedData {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Rate fixed on a contract at creation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FixedRate {
+    pub currency: [u8; 3],
+    pub currency_decimals: u8,
+    pub price: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl FixedRate {
+    pub const LEN: usize = 3 + 1 + 8 + 4 + 8;
+
+    /// Converts `amount` in reference minor units into base units of a mint
+    /// with `mint_decimals`, rounding down.
+    pub fn to_settlement(&self, amount: u64, mint_decimals: u8) -> Result<u64> {
+        let scale = mint_decimals as i32 + self.expo - self.currency_decimals as i32;
+        let factor = 10u128
+            .checked_pow(scale.unsigned_abs())
+            .ok_or(ErrorCode::MathOverflow)?;
+        let value = amount as u128 * self.price as u128;
+        let settled = if scale >= 0 {
+            value.checked_mul(factor).ok_or(ErrorCode::MathOverflow)?
+        } else {
+            value / factor
+        };
+        u64::try_from(settled).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+}
+
+/// Locks in the rate for a listing priced in `reference_currency` and settled
+/// in `mint`, or `None` when it is priced in the settlement mint.
+pub fn settlement_rate(
+    reference_currency: Option<[u8; 3]>,
+    mint: &Pubkey,
+    currency_feed: Option<&CurrencyFeed>,
+    price_feed: Option<&AccountInfo>,
+    now: i64,
+) -> Result<Option<FixedRate>> {
+    let currency = match reference_currency {
+        Some(currency) => currency,
+        None => return Ok(None),
+    };
+    let currency_feed = currency_feed
+        .filter(|feed| feed.currency == currency && feed.mint == *mint)
+        .ok_or(ErrorCode::CurrencyFeedRequired)?;
+    let price_feed = price_feed.ok_or(ErrorCode::CurrencyFeedRequired)?;
+    Ok(Some(currency_feed.read_rate(price_feed, now)?))
+}
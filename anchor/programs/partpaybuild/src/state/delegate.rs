@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// A staff key allowed to act for a vendor within its `permissions`.
+#[account]
+pub struct VendorDelegate {
+    pub vendor: Pubkey,
+    pub delegate: Pubkey,
+    pub permissions: u8,
+    pub bump: u8,
+}
+
+impl VendorDelegate {
+    pub const SEED_PREFIX: &'static [u8] = b"vendor_delegate";
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 1;
+
+    /// Upload and update equipment.
+    pub const CATALOG: u8 = 1 << 0;
+    /// Mark orders shipped.
+    pub const SHIPPING: u8 = 1 << 1;
+    /// Accept funding and withdraw vendor payouts.
+    pub const FINANCE: u8 = 1 << 2;
+    pub const ALL: u8 = Self::CATALOG | Self::SHIPPING | Self::FINANCE;
+
+    pub fn has(&self, permission: u8) -> bool {
+        self.permissions & permission == permission
+    }
+}
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
 
 #[account]
 pub struct Equipment {
     pub vendor: Pubkey,
     pub asset: Pubkey,
+    pub mint: Pubkey,
     pub unique_id: Pubkey,
     pub name: String,
     pub uri: String,
@@ -18,9 +20,71 @@ pub struct Equipment {
     pub status: EquipmentStatus,
     pub funders: Vec<Box<FunderInfo>>,
     pub delivery_status: DeliveryStatus,
+    /// When set, `price` and `minimum_deposit` are in this currency's minor
+    /// units and are converted to `mint` through its `CurrencyFeed` at contract
+    /// creation.
+    pub reference_currency: Option<[u8; 3]>,
+    /// Slot in the vendor's `VendorCatalogPage` index. Doubles as the
+    /// vendor-local sequence number in the PDA seeds, so the address does not
+    /// depend on anything that can be edited.
+    pub catalog_slot: u64,
+    /// Off sale: no new contracts or fundings, existing ones keep running.
+    pub delisted: bool,
+    /// Contracts on this listing that are not yet fully paid.
+    pub active_contracts: u64,
+    /// Deposit and funding escrows on this listing not yet released.
+    pub open_escrows: u64,
+    /// Bumped on every price change. Contracts and funder positions record
+    /// the version they were struck at.
+    pub terms_version: u32,
+    /// SKUs under this listing. The quantity counters above are totals
+    /// across all variants; `price` and `minimum_deposit` mirror variant 0.
+    pub variants: Vec<EquipmentVariant>,
+    /// Marketplace category the listing is indexed under, if any.
+    pub category: Option<Pubkey>,
+    /// Slot in the category's `CategoryIndexPage` index.
+    pub category_slot: u64,
+    pub tags: Vec<String>,
+    /// Upload time; starts the BNPL window of `PaymentPreference::Both`.
+    pub listed_at: i64,
+    /// Unit indices handed out so far. Only ever grows, so a cancelled order
+    /// never frees an index that a live contract could be given again.
+    pub units_issued: u64,
 }
 
+/// One SKU of a listing with its own pricing and stock. Variant 0 is created
+/// with the listing.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EquipmentVariant {
+    pub name: String,
+    pub price: u64,
+    pub minimum_deposit: u64,
+    pub total_quantity: u64,
+    pub funded_quantity: u64,
+    pub sold_quantity: u64,
+    pub funded_sold_quantity: u64,
+}
+
+impl EquipmentVariant {
+    pub const LEN: usize = (4 + 32) + 8 + 8 + 8 + 8 + 8 + 8;
+
+    pub fn available_for_bnpl(&self) -> u64 {
+        self.total_quantity
+            .saturating_sub(self.sold_quantity)
+            .saturating_sub(self.funded_sold_quantity)
+    }
+
+    pub fn available_for_funding(&self) -> u64 {
+        self.total_quantity
+            .saturating_sub(self.funded_quantity)
+            .saturating_sub(self.sold_quantity)
+    }
+}
+
+/// How buyers may pay for a listing. `Part` is BNPL only and `Full` is
+/// one-shot purchase only. `Both` takes BNPL for `timeout` seconds after
+/// listing; after that, stock that was not financed is full payment only.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum PaymentPreference {
     Part,
     Full,
@@ -44,6 +108,124 @@ pub struct FunderInfo {
     pub duration_seconds: i64,
     pub borrower: Option<Pubkey>,
     pub escrow: Pubkey,
+    pub criteria: FunderCriteria,
+    pub funder_return: FunderReturn,
+    pub amount_funded: u64,
+    pub units_sold: u64,
+    pub amount_collected: u64,
+    /// Price per unit the funder escrowed for its unsold units; buyers of
+    /// those units pay on this price even after the listing is repriced.
+    pub unit_price: u64,
+    /// Principal of the units sold so far, each at the price it was sold at,
+    /// so repricing the unsold units leaves it untouched.
+    pub sold_principal: u64,
+    /// What buyers of the units sold so far agreed to repay in total.
+    pub sold_repayable: u64,
+    pub terms_version: u32,
+    pub variant: u8,
+}
+
+impl FunderInfo {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 33 + 32 + FunderCriteria::LEN + FunderReturn::LEN + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 1;
+    pub const BPS_DENOMINATOR: u128 = 10_000;
+    pub const SECONDS_PER_YEAR: u128 = 31_536_000;
+
+    /// Total a buyer must agree to repay for one of this funder's units.
+    pub fn required_total(&self, price: u64) -> Result<u64> {
+        let price = price as u128;
+        let funder_return = match self.funder_return {
+            FunderReturn::Markup { bps } => price * bps as u128 / Self::BPS_DENOMINATOR,
+            FunderReturn::TargetApr { bps } => {
+                price * bps as u128 * self.duration_seconds.max(0) as u128
+                    / (Self::BPS_DENOMINATOR * Self::SECONDS_PER_YEAR)
+            }
+        };
+        let total = price.checked_add(funder_return).ok_or(ErrorCode::MathOverflow)?;
+        u64::try_from(total).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+
+    pub fn unsold_units(&self) -> u64 {
+        self.quantity.saturating_sub(self.units_sold)
+    }
+
+    pub fn principal_sold(&self) -> u64 {
+        self.sold_principal
+    }
+
+    /// Part of the collections that repaid principal, taking each collection
+    /// to carry principal and return in the same proportion as the sold units'
+    /// totals.
+    pub fn principal_collected(&self) -> Result<u64> {
+        if self.sold_repayable == 0 {
+            return Ok(0);
+        }
+        Ok((self.amount_collected as u128 * self.sold_principal as u128 / self.sold_repayable as u128) as u64)
+    }
+
+    /// Records `units` sold at `price` against the position, or released
+    /// again when `sold` is false.
+    pub fn record_sale(&mut self, units: u64, price: u64, sold: bool) -> Result<()> {
+        let principal = price.checked_mul(units).ok_or(ErrorCode::MathOverflow)?;
+        let repayable = self.required_total(price)?.checked_mul(units).ok_or(ErrorCode::MathOverflow)?;
+        if sold {
+            self.units_sold = self.units_sold.checked_add(units).ok_or(ErrorCode::MathOverflow)?;
+            self.sold_principal = self.sold_principal.checked_add(principal).ok_or(ErrorCode::MathOverflow)?;
+            self.sold_repayable = self.sold_repayable.checked_add(repayable).ok_or(ErrorCode::MathOverflow)?;
+        } else {
+            self.units_sold = self.units_sold.saturating_sub(units);
+            self.sold_principal = self.sold_principal.saturating_sub(principal);
+            self.sold_repayable = self.sold_repayable.saturating_sub(repayable);
+        }
+        Ok(())
+    }
+
+    /// Principal of the units sold so far that has not been repaid yet.
+    pub fn outstanding_principal(&self) -> Result<u64> {
+        Ok(self.principal_sold().saturating_sub(self.principal_collected()?))
+    }
+
+    /// Amount collected over the principal of the units sold so far.
+    pub fn realized_return(&self) -> i64 {
+        self.amount_collected as i64 - self.principal_sold() as i64
+    }
+
+    pub fn realized_yield_bps(&self) -> i64 {
+        let principal = self.principal_sold();
+        if principal == 0 {
+            return 0;
+        }
+        (self.realized_return() as i128 * Self::BPS_DENOMINATOR as i128 / principal as i128) as i64
+    }
+}
+
+/// The return a funder earns on each unit sold from its funded stock.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum FunderReturn {
+    Markup { bps: u16 },
+    TargetApr { bps: u16 },
+}
+
+impl Default for FunderReturn {
+    fn default() -> Self {
+        FunderReturn::Markup { bps: 0 }
+    }
+}
+
+impl FunderReturn {
+    pub const LEN: usize = 1 + 2;
+}
+
+/// Risk criteria a funder attaches to its funded units. A buyer picking this
+/// funder in `create_contract` must satisfy all of them.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct FunderCriteria {
+    pub min_credit_score: u64,
+    pub max_total_loans: Option<u64>,
+    pub kyc_attestor: Option<Pubkey>,
+}
+
+impl FunderCriteria {
+    pub const LEN: usize = 8 + 9 + 33;
 }
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum DeliveryStatus {
@@ -51,8 +233,99 @@ pub enum DeliveryStatus {
     Shipped,
     Delivered,
     Disputed,
+    Cancelled,
 }
 
 impl Equipment {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + (4 + 64) + (4 + 128) + 8 + 8 + 8 + 9 + 8 + 8 + 8 + 8 + 1 + (4 + 32 * 10) + 1;
+    pub const MAX_FUNDERS: usize = 10;
+    pub const MAX_VARIANTS: usize = 8;
+    pub const MAX_TAGS: usize = 5;
+    pub const MAX_TAG_LEN: usize = 16;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + (4 + 64) + (4 + 128) + 8 + 8 + 8 + 9 + 8 + 8 + 8 + 8 + 1 + (4 + FunderInfo::LEN * Self::MAX_FUNDERS) + 1 + 4 + 8 + 1 + 8 + 8 + 4 + (4 + EquipmentVariant::LEN * Self::MAX_VARIANTS) + 33 + 8 + (4 + (4 + Self::MAX_TAG_LEN) * Self::MAX_TAGS) + 8 + 8;
+
+    /// Reserves `quantity` consecutive unit indices and returns the first.
+    pub fn issue_units(&mut self, quantity: u64) -> Result<u64> {
+        let first = self.units_issued;
+        self.units_issued = first.checked_add(quantity).ok_or(ErrorCode::MathOverflow)?;
+        Ok(first)
+    }
+
+    /// Units a buyer can still take on BNPL, from the vendor's own stock or
+    /// from funded units not yet sold.
+    pub fn available_for_bnpl(&self) -> u64 {
+        self.total_quantity
+            .saturating_sub(self.sold_quantity)
+            .saturating_sub(self.funded_sold_quantity)
+    }
+
+    /// Units neither funded nor sold by the vendor directly.
+    pub fn available_for_funding(&self) -> u64 {
+        self.total_quantity
+            .saturating_sub(self.funded_quantity)
+            .saturating_sub(self.sold_quantity)
+    }
+
+    pub fn variant(&self, index: u8) -> Result<&EquipmentVariant> {
+        self.variants.get(index as usize).ok_or(ErrorCode::InvalidVariant.into())
+    }
+
+    pub fn variant_mut(&mut self, index: u8) -> Result<&mut EquipmentVariant> {
+        self.variants.get_mut(index as usize).ok_or(ErrorCode::InvalidVariant.into())
+    }
+
+    /// Whether vendor stock can still be financed, by a BNPL contract or a
+    /// funder, at `now`.
+    pub fn accepts_bnpl(&self, now: i64) -> bool {
+        match self.payment_preference {
+            PaymentPreference::Part => true,
+            PaymentPreference::Full => false,
+            PaymentPreference::Both { timeout } => now < self.listed_at.saturating_add(timeout),
+        }
+    }
+
+    /// Whether a one-shot full payment is accepted.
+    pub fn accepts_full_payment(&self) -> bool {
+        !matches!(self.payment_preference, PaymentPreference::Part)
+    }
+
+    /// The preference in force at `now`: `Both` reads as `Full` once its BNPL
+    /// window has closed.
+    pub fn effective_payment_preference(&self, now: i64) -> PaymentPreference {
+        match self.payment_preference {
+            PaymentPreference::Both { .. } if !self.accepts_bnpl(now) => PaymentPreference::Full,
+            ref preference => preference.clone(),
+        }
+    }
+
+    /// Lowest and highest variant price.
+    pub fn price_range(&self) -> (u64, u64) {
+        let prices = self.variants.iter().map(|v| v.price);
+        (
+            prices.clone().min().unwrap_or(self.price),
+            prices.max().unwrap_or(self.price),
+        )
+    }
+
+    /// Every sale is paid off and every escrow released.
+    pub fn is_settled(&self) -> bool {
+        self.active_contracts == 0 && self.open_escrows == 0
+    }
+
+    /// Recomputes `status` after a sale or a stock change. A listing that was
+    /// sold out goes back to its funding state once it has stock again.
+    pub fn refresh_status(&mut self) {
+        if self.available_for_bnpl() == 0 {
+            self.status = EquipmentStatus::Sold;
+        } else if self.available_for_funding() == 0 && self.funded_quantity > self.funded_sold_quantity {
+            self.status = EquipmentStatus::PartiallySold;
+        } else if matches!(self.status, EquipmentStatus::Sold | EquipmentStatus::PartiallySold) {
+            self.status = if self.funded_quantity == 0 {
+                EquipmentStatus::Available
+            } else if self.funders.iter().any(|f| f.borrower.is_none()) {
+                EquipmentStatus::Funded
+            } else {
+                EquipmentStatus::Reserved
+            };
+        }
+    }
 }
\ No newline at end of file
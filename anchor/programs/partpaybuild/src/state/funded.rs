@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::equipment::FunderReturn;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct FunderEquipmentResponse {
@@ -13,8 +14,29 @@ pub struct FunderEquipmentInfo {
     pub price: u64,
     pub uri: String,
     pub asset: Pubkey,
+    pub variant: u8,
     pub funded_quantity: u64,
     pub total_quantity: u64,
     pub minimum_deposit: u64,
     pub duration_seconds: i64,
+    pub funder_return: FunderReturn,
+    pub amount_funded: u64,
+    pub units_sold: u64,
+    pub amount_collected: u64,
+    pub realized_return: i64,
+    pub realized_yield_bps: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct FunderPortfolioResponse {
+    pub position_count: u64,
+    pub contract_count: u64,
+    pub deployed_capital: u64,
+    pub outstanding_principal: u64,
+    pub overdue_1_30_days: u64,
+    pub overdue_31_60_days: u64,
+    pub overdue_over_60_days: u64,
+    pub repaid_to_date: u64,
+    pub defaults: u64,
+    pub current_yield_bps: i64,
 }
\ No newline at end of file
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct KycAttestation {
+    pub attestor: Pubkey,
+    pub subject: Pubkey,
+    pub issued_at: i64,
+    pub expires_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl KycAttestation {
+    pub const SEED_PREFIX: &'static [u8] = b"kyc_attestation";
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 9 + 1;
+
+    pub fn is_valid_at(&self, now: i64) -> bool {
+        self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
 
 #[account]
 pub struct Marketplace {
@@ -6,4 +7,115 @@ pub struct Marketplace {
     pub collection: Pubkey,
     pub name: String,
     pub uri: String,
+    pub vendor_count: u64,
+    pub rules: MarketplaceRules,
+    pub fees: MarketplaceFees,
+}
+
+impl Marketplace {
+    pub const LEN: usize = 8 + 32 + 32 + (4 + 32) + (4 + 200) + 8 + MarketplaceRules::LEN + MarketplaceFees::LEN;
+}
+
+/// Take rates, in basis points, the marketplace treasury keeps from each flow.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct MarketplaceFees {
+    pub deposit_fee_bps: u16,
+    pub installment_fee_bps: u16,
+    pub funding_fee_bps: u16,
+}
+
+impl MarketplaceFees {
+    pub const LEN: usize = 2 + 2 + 2;
+
+    pub fn bps(&self, kind: FeeKind) -> u16 {
+        match kind {
+            FeeKind::Deposit => self.deposit_fee_bps,
+            FeeKind::Installment => self.installment_fee_bps,
+            FeeKind::Funding => self.funding_fee_bps,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum FeeKind {
+    Deposit,
+    Installment,
+    Funding,
+}
+
+/// Terms every contract on this marketplace's listings must respect.
+/// A zero value leaves that rule unset.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct MarketplaceRules {
+    pub min_deposit_bps: u16,
+    pub max_duration_seconds: i64,
+    pub max_installments: u8,
+}
+
+impl MarketplaceRules {
+    pub const LEN: usize = 2 + 8 + 1;
+
+    pub fn validate_contract(
+        &self,
+        total_amount: u64,
+        deposit: u64,
+        duration: i64,
+        installment_count: u64,
+    ) -> Result<()> {
+        let min_deposit = (total_amount as u128 * self.min_deposit_bps as u128 / 10_000) as u64;
+        require!(deposit >= min_deposit, ErrorCode::DepositBelowMinimum);
+        if self.max_duration_seconds > 0 {
+            require!(duration <= self.max_duration_seconds, ErrorCode::DurationExceedsMax);
+        }
+        if self.max_installments > 0 {
+            require!(
+                installment_count <= self.max_installments as u64,
+                ErrorCode::TooManyInstallments
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A vendor's request to join a marketplace. Once approved it is the record
+/// that the vendor's collection is verified under the marketplace.
+#[account]
+pub struct MarketplaceApplication {
+    pub marketplace: Pubkey,
+    pub vendor: Pubkey,
+    pub collection: Pubkey,
+    pub status: ApplicationStatus,
+    pub applied_at: i64,
+    pub bump: u8,
+}
+
+impl MarketplaceApplication {
+    pub const SEED_PREFIX: &'static [u8] = b"marketplace_application";
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 1 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ApplicationStatus {
+    Pending,
+    Approved,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MarketplaceEquipmentInfo {
+    pub equipment_pda: Pubkey,
+    pub vendor: Pubkey,
+    pub name: String,
+    pub price: u64,
+    pub uri: String,
+    pub asset: Pubkey,
+    pub variant_count: u8,
+    pub min_price: u64,
+    pub max_price: u64,
+    pub available_for_bnpl: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MarketplaceEquipmentResponse {
+    pub total_count: u64,
+    pub equipment: Vec<MarketplaceEquipmentInfo>,
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 pub mod marketplace;
+pub mod config;
 pub mod vendor;
 pub mod borrower;
 pub mod contract;
@@ -6,8 +7,16 @@ pub mod credit_score;
 pub mod equipment;
 pub mod funded;
 pub mod escrow;
+pub mod kyc;
+pub mod offer;
+pub mod currency;
+pub mod delegate;
+pub mod catalog;
+pub mod category;
+pub mod cart;
 
 pub use marketplace::*;
+pub use config::*;
 pub use vendor::*;
 pub use borrower::*;
 pub use contract::*;
@@ -15,3 +24,10 @@ pub use credit_score::*;
 pub use equipment::*;
 pub use funded::*;
 pub use escrow::*;
+pub use kyc::*;
+pub use offer::*;
+pub use currency::*;
+pub use delegate::*;
+pub use catalog::*;
+pub use category::*;
+pub use cart::*;
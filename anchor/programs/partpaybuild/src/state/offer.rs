@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::equipment::{FunderCriteria, FunderReturn};
+
+/// A standing offer by a funder to finance units on the terms below. The
+/// offer's vault holds `max_units * max_unit_price` until units are accepted.
+#[account]
+pub struct FundingOffer {
+    pub funder: Pubkey,
+    pub offer_unique_id: Pubkey,
+    pub vendor: Option<Pubkey>,
+    pub equipment: Option<Pubkey>,
+    /// Restricts the offer to listings in this marketplace category.
+    pub category: Option<Pubkey>,
+    pub mint: Pubkey,
+    pub max_units: u64,
+    pub units_accepted: u64,
+    pub max_unit_price: u64,
+    pub minimum_deposit: u64,
+    pub duration_seconds: i64,
+    pub criteria: FunderCriteria,
+    pub funder_return: FunderReturn,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl FundingOffer {
+    pub const SEED_PREFIX: &'static [u8] = b"funding_offer";
+    pub const LEN: usize = 8 + // Discriminator
+        32 + // funder
+        32 + // offer_unique_id
+        33 + // vendor
+        33 + // equipment
+        33 + // category
+        32 + // mint
+        8 +  // max_units
+        8 +  // units_accepted
+        8 +  // max_unit_price
+        8 +  // minimum_deposit
+        8 +  // duration_seconds
+        FunderCriteria::LEN +
+        FunderReturn::LEN +
+        8 +  // expires_at
+        1;   // bump
+
+    pub fn remaining_units(&self) -> u64 {
+        self.max_units - self.units_accepted
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
@@ -1,4 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::{
+    constants::VENDOR_SEED,
+    state::{delegate::VendorDelegate, equipment::EquipmentStatus, marketplace::Marketplace},
+};
 
 #[account]
 pub struct Vendor {
@@ -7,14 +11,85 @@ pub struct Vendor {
     pub name: String,
     pub uri: String,
     pub marketplace: Option<Pubkey>,
-    pub equipments: Vec<Pubkey>,
     pub status: VendorStatus,
     pub unique_id: Pubkey,
     pub collection_unique_id: Pubkey,
-    pub equipment_count: u64,
+    /// Listings currently in the catalog.
+    pub listing_count: u64,
+    /// Units across those listings.
+    pub unit_count: u64,
+    /// Number of slots handed out in the `VendorCatalogPage` index.
+    pub catalog_slots: u64,
+    pub pending_authority: Option<Pubkey>,
+    /// Authority the vendor was created with; part of the PDA seeds, so it
+    /// never changes when the authority is transferred.
+    pub creator: Pubkey,
+    pub bump: u8,
 }
 
-#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+impl Vendor {
+    pub const LEN: usize = 8 + // Discriminator
+        32 + // authority
+        32 + // collection
+        (4 + 32) + // name
+        (4 + 200) + // uri
+        33 + // marketplace
+        1 +  // status
+        32 + // unique_id
+        32 + // collection_unique_id
+        8 +  // listing_count
+        8 +  // unit_count
+        8 +  // catalog_slots
+        33 + // pending_authority
+        32 + // creator
+        1;   // bump
+
+    /// Seeds for signing as the vendor PDA, which owns the listing assets.
+    pub fn signer_seeds(&self) -> [&[u8]; 4] {
+        [
+            VENDOR_SEED,
+            self.creator.as_ref(),
+            self.unique_id.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+
+    /// Whether `signer` oversees this vendor: the authority of the marketplace
+    /// it belongs to, or the program admin for vendors outside a marketplace.
+    pub fn is_overseen_by(
+        &self,
+        signer: &Pubkey,
+        marketplace: Option<&Account<Marketplace>>,
+        admin: &Pubkey,
+    ) -> bool {
+        match self.marketplace {
+            Some(vendor_marketplace) => marketplace
+                .is_some_and(|m| m.key() == vendor_marketplace && m.authority == *signer),
+            None => signer == admin,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == VendorStatus::Active
+    }
+
+    /// Whether `signer` may act for this vendor: the authority always can, a
+    /// delegate only with `permission`.
+    pub fn authorizes(
+        &self,
+        vendor_key: &Pubkey,
+        signer: &Pubkey,
+        delegate: Option<&VendorDelegate>,
+        permission: u8,
+    ) -> bool {
+        *signer == self.authority
+            || delegate.is_some_and(|d| {
+                d.vendor == *vendor_key && d.delegate == *signer && d.has(permission)
+            })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, PartialEq)]
 pub enum VendorStatus {
     Active,
     Suspended,
@@ -28,11 +103,23 @@ pub struct EquipmentInfo {
     pub uri: String,
     pub asset: Pubkey,
     pub index: u64,
+    pub status: EquipmentStatus,
+    pub total_quantity: u64,
+    pub funded_quantity: u64,
+    pub sold_quantity: u64,
+    pub funded_sold_quantity: u64,
+    pub available_for_bnpl: u64,
+    pub available_for_funding: u64,
+    /// Price range across variants; quantities above are already totals.
+    pub variant_count: u8,
+    pub min_price: u64,
+    pub max_price: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct VendorEquipmentResponse {
-    pub total_count: u64,
+    pub listing_count: u64,
+    pub unit_count: u64,
     pub equipment: Vec<EquipmentInfo>,
     pub failed_loads: Vec<u64>,
 }
\ No newline at end of file
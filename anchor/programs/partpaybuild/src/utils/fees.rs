@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::{
+    constants::TREASURY_SEED,
+    errors::ErrorCode,
+    state::{
+        marketplace::{FeeKind, Marketplace},
+        vendor::Vendor,
+    },
+};
+
+pub fn calculate_fee(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / 10_000) as u64
+}
+
+/// Fee owed to the treasury of the vendor's marketplace on `amount`. Vendors
+/// outside a marketplace pay no fees.
+pub fn marketplace_fee(
+    vendor: &Vendor,
+    marketplace: Option<&Account<Marketplace>>,
+    treasury: Option<&InterfaceAccount<TokenAccount>>,
+    mint: &Pubkey,
+    kind: FeeKind,
+    amount: u64,
+) -> Result<u64> {
+    let vendor_marketplace = match vendor.marketplace {
+        Some(key) => key,
+        None => return Ok(0),
+    };
+    let marketplace = marketplace
+        .filter(|m| m.key() == vendor_marketplace)
+        .ok_or(ErrorCode::MarketplaceRequired)?;
+
+    let fee = calculate_fee(amount, marketplace.fees.bps(kind));
+    if fee > 0 {
+        // Only the treasury PDA from `initialize_treasury` is drained by
+        // `withdraw_fees`, so fees may not go to any other marketplace account.
+        let treasury = treasury.ok_or(ErrorCode::InvalidTreasury)?;
+        let (expected_treasury, _) = Pubkey::find_program_address(
+            &[TREASURY_SEED, marketplace.key().as_ref(), mint.as_ref()],
+            &crate::ID,
+        );
+        require!(
+            treasury.key() == expected_treasury
+                && treasury.owner == marketplace.key()
+                && treasury.mint == *mint,
+            ErrorCode::InvalidTreasury
+        );
+    }
+    Ok(fee)
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::ErrorCode,
+    state::{
+        equipment::{DeliveryStatus, Equipment, EquipmentStatus, FunderInfo},
+        escrow::Escrow,
+    },
+};
+
+/// Checks that `quantity_to_fund` units of `variant` can be funded and returns
+/// the amount the funder has to lock in escrow for them.
+pub fn validate_funding(equipment: &Equipment, variant: u8, quantity_to_fund: u64) -> Result<u64> {
+    let sku = equipment.variant(variant)?;
+    require!(
+        quantity_to_fund <= sku.available_for_funding(),
+        ErrorCode::InsufficientQuantity
+    );
+    require!(
+        equipment.status == EquipmentStatus::Available ||
+        equipment.status == EquipmentStatus::Reserved ||
+        equipment.status == EquipmentStatus::Funded,
+        ErrorCode::EquipmentNotAvailable
+    );
+    require!(
+        equipment.funders.len() < Equipment::MAX_FUNDERS,
+        ErrorCode::TooManyFunders
+    );
+    require!(!equipment.delisted, ErrorCode::EquipmentDelisted);
+    require!(
+        equipment.accepts_bnpl(Clock::get()?.unix_timestamp),
+        ErrorCode::FullPaymentOnly
+    );
+    // Funders commit settlement tokens up front, which has no fixed rate yet.
+    require!(
+        equipment.reference_currency.is_none(),
+        ErrorCode::ReferencePricedFunding
+    );
+
+    Ok(sku.price.checked_mul(quantity_to_fund).ok_or(ErrorCode::MathOverflow)?)
+}
+
+/// Records a funded position on the equipment and its escrow once the
+/// funds have been moved into the escrow token account. `escrow_amount` is
+/// what reached the escrow after marketplace fees.
+pub fn record_funding(
+    equipment: &mut Equipment,
+    equipment_key: Pubkey,
+    escrow: &mut Escrow,
+    escrow_amount: u64,
+    funder_info: FunderInfo,
+    status: EquipmentStatus,
+) -> Result<()> {
+    escrow.equipment = equipment_key;
+    escrow.funder = funder_info.funder;
+    escrow.vendor = equipment.vendor;
+    escrow.amount = escrow_amount;
+    escrow.is_released = false;
+
+    equipment.variant_mut(funder_info.variant)?.funded_quantity += funder_info.quantity;
+    equipment.open_escrows += 1;
+    equipment.funded_quantity += funder_info.quantity;
+    equipment.funders.push(Box::new(funder_info));
+    equipment.status = status;
+    equipment.delivery_status = DeliveryStatus::Pending;
+    Ok(())
+}
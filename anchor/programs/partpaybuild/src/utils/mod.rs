@@ -1,3 +1,7 @@
 pub mod calculate_end_date;
+pub mod fees;
+pub mod funding;
 pub mod load_equipment;
+pub mod orders;
+pub mod transfer;
 pub mod validation;
\ No newline at end of file
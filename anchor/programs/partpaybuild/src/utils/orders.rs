@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use mpl_core::{instructions::CreateV2Builder, types::DataState};
+use crate::{
+    constants::UNIT_ASSET_SEED,
+    errors::ErrorCode,
+    state::{
+        contract::BNPLContract,
+        equipment::{DeliveryStatus, Equipment},
+    },
+};
+
+/// Checks the asset accounts for `units` units starting at `first_offset`
+/// within the order: `first` for the first unit, then `rest` in order.
+/// Returns each account with its PDA bump.
+pub fn unit_asset_accounts<'info>(
+    first: &AccountInfo<'info>,
+    rest: &[AccountInfo<'info>],
+    program_id: &Pubkey,
+    contract_key: &Pubkey,
+    first_offset: u64,
+    units: u64,
+) -> Result<Vec<(AccountInfo<'info>, u8)>> {
+    require!(rest.len() as u64 + 1 >= units, ErrorCode::InvalidUnitAsset);
+    std::iter::once(first)
+        .chain(rest.iter())
+        .take(units as usize)
+        .enumerate()
+        .map(|(i, account)| {
+            let offset = first_offset + i as u64;
+            let (expected, bump) = Pubkey::find_program_address(
+                &[UNIT_ASSET_SEED, contract_key.as_ref(), &offset.to_le_bytes()],
+                program_id,
+            );
+            require_keys_eq!(account.key(), expected, ErrorCode::InvalidUnitAsset);
+            Ok((account.clone(), bump))
+        })
+        .collect()
+}
+
+/// Mints the Core asset for a delivered unit to `owner`. The asset is a PDA
+/// of the contract and the unit's offset in it, so each unit gets exactly one.
+#[allow(clippy::too_many_arguments)]
+pub fn mint_unit_asset<'info>(
+    unit_asset: &AccountInfo<'info>,
+    unit_asset_bump: u8,
+    contract_key: &Pubkey,
+    unit_offset: u64,
+    equipment: &Equipment,
+    payer: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    vendor: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    mpl_core_program: &AccountInfo<'info>,
+) -> Result<Pubkey> {
+    let create_unit_ix = CreateV2Builder::new()
+        .asset(unit_asset.key())
+        .payer(payer.key())
+        .owner(Some(owner.key()))
+        .update_authority(Some(vendor.key()))
+        .system_program(system_program.key())
+        .data_state(DataState::AccountState)
+        .name(equipment.name.clone())
+        .uri(equipment.uri.clone())
+        .plugins(vec![])
+        .external_plugin_adapters(vec![])
+        .instruction();
+
+    invoke_signed(
+        &create_unit_ix,
+        &[
+            unit_asset.clone(),
+            payer.clone(),
+            owner.clone(),
+            vendor.clone(),
+            system_program.clone(),
+            mpl_core_program.clone(),
+        ],
+        &[&[
+            UNIT_ASSET_SEED,
+            contract_key.as_ref(),
+            &unit_offset.to_le_bytes(),
+            &[unit_asset_bump],
+        ]],
+    )
+    .map_err(|e| {
+        msg!("Error invoking Metaplex Core program: {:?}", e);
+        error!(ErrorCode::MetaplexError)
+    })?;
+
+    Ok(unit_asset.key())
+}
+
+/// Share of the order's remaining escrow that belongs to `units` of its
+/// undelivered units.
+pub fn escrow_share(escrow_amount: u64, contract: &BNPLContract, units: u64) -> u64 {
+    let undelivered = contract.undelivered_quantity();
+    if units >= undelivered {
+        return escrow_amount;
+    }
+    (escrow_amount as u128 * units as u128 / undelivered as u128) as u64
+}
+
+/// Records `units` of an order as delivered once `received` from its escrow
+/// has reached the payee. The order is delivered when its last unit is.
+pub fn complete_delivery(
+    equipment: &mut Equipment,
+    contract: &mut BNPLContract,
+    first_unit_asset: Pubkey,
+    received: u64,
+    units: u64,
+) {
+    if let Some(funder_escrow) = contract.funder_escrow {
+        if let Some(funder_info) = equipment.funders.iter_mut().find(|f| f.escrow == funder_escrow) {
+            funder_info.amount_collected += received;
+        }
+    }
+    contract.unit_asset.get_or_insert(first_unit_asset);
+    contract.delivered_quantity += units;
+    equipment.delivery_status = DeliveryStatus::Delivered;
+    if contract.undelivered_quantity() == 0 {
+        contract.delivery_status = DeliveryStatus::Delivered;
+        equipment.open_escrows = equipment.open_escrows.saturating_sub(1);
+    }
+}
+
+/// Unwinds an order that will not be delivered: its undelivered units go back
+/// on sale and the contract is closed out. The escrow refund is up to the caller.
+pub fn unwind_order(equipment: &mut Equipment, contract: &mut BNPLContract) -> Result<()> {
+    release_undelivered_units(equipment, contract)?;
+    if !contract.is_completed {
+        equipment.active_contracts = equipment.active_contracts.saturating_sub(1);
+    }
+    equipment.open_escrows = equipment.open_escrows.saturating_sub(1);
+    equipment.refresh_status();
+
+    contract.is_completed = true;
+    contract.delivery_status = DeliveryStatus::Cancelled;
+    Ok(())
+}
+
+/// Settles an order whose undelivered units were refunded from its escrow.
+/// Without deliveries the order is unwound; otherwise it shrinks to the
+/// delivered units, its total drops by the undelivered units' share and it
+/// completes if that leaves nothing to pay.
+pub fn refund_undelivered(equipment: &mut Equipment, contract: &mut BNPLContract, refunded: u64) -> Result<()> {
+    if contract.delivered_quantity == 0 {
+        return unwind_order(equipment, contract);
+    }
+    let undelivered = contract.undelivered_quantity();
+    let total_share = (contract.total_amount as u128 * undelivered as u128 / contract.quantity as u128) as u64;
+    release_undelivered_units(equipment, contract)?;
+    equipment.open_escrows = equipment.open_escrows.saturating_sub(1);
+    equipment.refresh_status();
+
+    contract.total_amount = contract.total_amount.saturating_sub(total_share);
+    contract.amount_paid = contract.amount_paid.saturating_sub(refunded);
+    contract.deposit = contract.deposit.saturating_sub(refunded);
+    contract.quantity = contract.delivered_quantity;
+    contract.delivery_status = DeliveryStatus::Delivered;
+    if !contract.is_completed && contract.amount_paid >= contract.total_amount {
+        contract.is_completed = true;
+        equipment.active_contracts = equipment.active_contracts.saturating_sub(1);
+    }
+    Ok(())
+}
+
+/// Puts an order's undelivered units back on sale.
+fn release_undelivered_units(equipment: &mut Equipment, contract: &BNPLContract) -> Result<()> {
+    let units = contract.undelivered_quantity();
+    match contract.funder_escrow {
+        Some(funder_escrow) => {
+            let funder_info = equipment
+                .funders
+                .iter_mut()
+                .find(|f| f.escrow == funder_escrow)
+                .ok_or(ErrorCode::InvalidEscrow)?;
+            funder_info.record_sale(units, contract.unit_price, false)?;
+            let sku = equipment.variant_mut(contract.variant)?;
+            sku.funded_sold_quantity = sku.funded_sold_quantity.saturating_sub(units);
+            equipment.funded_sold_quantity = equipment.funded_sold_quantity.saturating_sub(units);
+        }
+        None => {
+            let sku = equipment.variant_mut(contract.variant)?;
+            sku.sold_quantity = sku.sold_quantity.saturating_sub(units);
+            equipment.sold_quantity = equipment.sold_quantity.saturating_sub(units);
+        }
+    }
+    Ok(())
+}
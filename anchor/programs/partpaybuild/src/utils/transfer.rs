@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, get_mint_extension_data, spl_token_2022::extension::transfer_fee::TransferFeeConfig,
+    Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::errors::ErrorCode;
+
+/// `transfer_checked` into `to`, returning the amount that actually arrived.
+/// Token-2022 mints with a transfer fee withhold part of `amount` in the
+/// destination, so callers must account with the returned value.
+pub fn transfer_checked_received<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    from: AccountInfo<'info>,
+    to: &mut InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<u64> {
+    let balance_before = to.amount;
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from,
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+        mint.decimals,
+    )?;
+    to.reload()?;
+    to.amount
+        .checked_sub(balance_before)
+        .ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// Whether `mint` carries the Token-2022 transfer fee extension.
+pub fn has_transfer_fee(mint: &InterfaceAccount<Mint>) -> bool {
+    get_mint_extension_data::<TransferFeeConfig>(&mint.to_account_info()).is_ok()
+}
+
+/// Amount to send so that `net_amount` arrives after the mint's transfer fee.
+pub fn gross_up_for_transfer_fee(mint: &InterfaceAccount<Mint>, net_amount: u64) -> Result<u64> {
+    let config = match get_mint_extension_data::<TransferFeeConfig>(&mint.to_account_info()) {
+        Ok(config) => config,
+        Err(_) => return Ok(net_amount),
+    };
+    let fee = config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    net_amount.checked_add(fee).ok_or(ErrorCode::MathOverflow.into())
+}
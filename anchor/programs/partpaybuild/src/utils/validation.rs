@@ -1,5 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::errors::ErrorCode;
+use crate::{
+    errors::ErrorCode,
+    state::{
+        borrower::Borrower,
+        credit_score::CreditScore,
+        equipment::FunderCriteria,
+        kyc::KycAttestation,
+    },
+};
 
 pub fn validate_string_length(string: &str, min: usize, max: usize) -> Result<()> {
     require!(
@@ -41,4 +49,41 @@ pub fn validate_insurance_premium(premium: Option<u64>) -> Result<()> {
 pub fn validate_installment_frequency(frequency: u64) -> Result<()> {
     require!(frequency > 0, ErrorCode::InvalidInstallmentFrequency);
     Ok(())
+}
+
+pub fn validate_funder_criteria(
+    criteria: &FunderCriteria,
+    buyer: &Pubkey,
+    borrower: Option<&Borrower>,
+    credit_score: Option<&CreditScore>,
+    kyc_attestation: Option<&KycAttestation>,
+    now: i64,
+) -> Result<()> {
+    let (borrower, credit_score) = match (borrower, credit_score) {
+        (Some(borrower), Some(credit_score)) => (borrower, credit_score),
+        _ => return err!(ErrorCode::BorrowerProfileRequired),
+    };
+    require!(borrower.authority == *buyer, ErrorCode::BorrowerMismatch);
+    require!(
+        credit_score.borrower == borrower.borrower_pubkey,
+        ErrorCode::InvalidCreditScorePda
+    );
+
+    require!(
+        credit_score.score >= criteria.min_credit_score,
+        ErrorCode::CreditScoreTooLow
+    );
+    if let Some(max_loans) = criteria.max_total_loans {
+        require!(borrower.total_loans < max_loans, ErrorCode::TooManyLoans);
+    }
+    if let Some(attestor) = criteria.kyc_attestor {
+        let attestation = kyc_attestation.ok_or(ErrorCode::KycAttestationRequired)?;
+        require!(
+            attestation.attestor == attestor
+                && attestation.subject == *buyer
+                && attestation.is_valid_at(now),
+            ErrorCode::KycAttestationRequired
+        );
+    }
+    Ok(())
 }
\ No newline at end of file
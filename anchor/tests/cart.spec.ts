@@ -0,0 +1,95 @@
+import { BN } from '@coral-xyz/anchor'
+import { ASSOCIATED_TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js'
+import {
+  MIN_DEPOSIT,
+  Market,
+  PRICE,
+  configPda,
+  pda,
+  program,
+  setupMarket,
+  teardownMarket,
+  writable,
+} from './fixtures'
+
+describe('cart checkout', () => {
+  let market: Market
+
+  beforeAll(async () => {
+    market = await setupMarket()
+  })
+
+  afterAll(async () => {
+    await teardownMarket(market)
+  })
+
+  it('make_cart_payment completes the cart and releases each listing once', async () => {
+    const { buyer, mint, vendor, buyerTokenAccount, vendorTokenAccount, borrowerPda } = market
+    const first = await market.uploadEquipment(2)
+    const second = await market.uploadEquipment(2)
+    const cartUniqueId = Keypair.generate().publicKey
+    const cart = pda([Buffer.from('cart_contract'), buyer.publicKey.toBuffer(), cartUniqueId.toBuffer()])
+
+    await program.methods
+      .createCartContract(
+        cartUniqueId,
+        [
+          { variant: 0, quantity: new BN(1) },
+          { variant: 0, quantity: new BN(2) },
+        ],
+        PRICE.muln(3),
+        MIN_DEPOSIT.muln(3),
+        { weekly: {} }
+      )
+      .accountsPartial({
+        cart,
+        vendor,
+        buyer: buyer.publicKey,
+        usdcMint: mint,
+        buyerTokenAccount,
+        cartVault: market.escrowAta(cart),
+        borrower: borrowerPda,
+        marketplace: null,
+        treasuryTokenAccount: null,
+        tokenProgram: market.tokenProgram,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        config: configPda,
+      })
+      .remainingAccounts([writable(first), writable(second)])
+      .signers([buyer])
+      .rpc()
+    expect(await market.totalLoans()).toBe(1)
+
+    const created = await program.account.cartContract.fetch(cart)
+    const remaining = created.totalAmount.sub(created.amountPaid)
+    const payment = (listings: PublicKey[]) =>
+      program.methods
+        .makeCartPayment(remaining)
+        .accountsPartial({
+          cart,
+          vendor,
+          buyer: buyer.publicKey,
+          usdcMint: mint,
+          buyerTokenAccount,
+          vendorTokenAccount,
+          marketplace: null,
+          treasuryTokenAccount: null,
+          borrowerProfile: borrowerPda,
+          tokenProgram: market.tokenProgram,
+          config: configPda,
+        })
+        .remainingAccounts(listings.map(writable))
+        .signers([buyer])
+        .rpc()
+
+    await expect(payment([first, first])).rejects.toThrow(/InvalidEquipment/)
+    await payment([first, second])
+
+    expect((await program.account.cartContract.fetch(cart)).isCompleted).toBe(true)
+    expect((await program.account.equipment.fetch(first)).activeContracts.toNumber()).toBe(0)
+    expect((await program.account.equipment.fetch(second)).activeContracts.toNumber()).toBe(0)
+    expect(await market.totalLoans()).toBe(0)
+  })
+})
@@ -0,0 +1,47 @@
+import { PublicKey } from '@solana/web3.js'
+import { Market, pda, program, setupMarket, teardownMarket } from './fixtures'
+
+describe('vendor catalog pages', () => {
+  let market: Market
+
+  const catalogPage = (page: number) => {
+    const index = Buffer.alloc(4)
+    index.writeUInt32LE(page)
+    return pda([Buffer.from('vendor_catalog_page'), market.vendor.toBuffer(), index])
+  }
+  const readPage = (page: number, listings: PublicKey[]) =>
+    program.methods
+      .getAllVendorEquipment(page)
+      .accountsPartial({ vendor: market.vendor, catalogPage: catalogPage(page) })
+      .remainingAccounts(listings.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .view()
+
+  beforeAll(async () => {
+    market = await setupMarket()
+  })
+
+  afterAll(async () => {
+    await teardownMarket(market)
+  })
+
+  it('get_all_vendor_equipment reads the listings of a page in slot order', async () => {
+    const first = await market.uploadEquipment(1)
+    const second = await market.uploadEquipment(3)
+
+    const response = await readPage(0, [first, second])
+
+    expect(response.listingCount.toNumber()).toBe(2)
+    expect(response.equipment.map((listing) => listing.index.toNumber())).toEqual([0, 1])
+    expect(response.equipment[1].totalQuantity.toNumber()).toBe(3)
+    expect(response.failedLoads).toEqual([])
+
+    // Accounts that do not match the page's index are reported, not trusted.
+    const swapped = await readPage(0, [second, first])
+    expect(swapped.equipment).toEqual([])
+    expect(swapped.failedLoads.map((slot) => slot.toNumber())).toEqual([0, 1])
+  })
+
+  it('get_all_vendor_equipment rejects a page that has not been opened', async () => {
+    await expect(readPage(1, [])).rejects.toThrow()
+  })
+})
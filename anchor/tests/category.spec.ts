@@ -0,0 +1,106 @@
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, Transaction } from '@solana/web3.js'
+import { MPL_CORE_PROGRAM_ID, Market, configPda, payer, pda, program, provider, setupMarket, teardownMarket } from './fixtures'
+
+describe('equipment categories', () => {
+  const curator = Keypair.generate()
+  const marketplace = pda([Buffer.from('marketplace'), curator.publicKey.toBuffer()])
+  const category = pda([Buffer.from('equipment_category'), marketplace.toBuffer(), Buffer.from('irrigation')])
+  let market: Market
+
+  const categoryPage = (page: number) => {
+    const index = Buffer.alloc(4)
+    index.writeUInt32LE(page)
+    return pda([Buffer.from('category_index_page'), category.toBuffer(), index])
+  }
+  const readPage = (listings: PublicKey[], tag: string | null = null) =>
+    program.methods
+      .getEquipmentByCategory(0, tag)
+      .accountsPartial({ category, categoryPage: categoryPage(0) })
+      .remainingAccounts(listings.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .view()
+
+  beforeAll(async () => {
+    market = await setupMarket()
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: payer.publicKey, toPubkey: curator.publicKey, lamports: LAMPORTS_PER_SOL })
+      )
+    )
+
+    await program.methods
+      .createMarketplace('Farm Market', 'https://example.com/market.json')
+      .accountsPartial({
+        marketplace,
+        marketplaceCollection: pda([Buffer.from('marketplace_collection'), marketplace.toBuffer()]),
+        authority: curator.publicKey,
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+        mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        config: configPda,
+      })
+      .signers([curator])
+      .rpc()
+
+    const application = pda([Buffer.from('marketplace_application'), marketplace.toBuffer(), market.vendor.toBuffer()])
+    await program.methods
+      .applyToMarketplace()
+      .accountsPartial({
+        marketplace,
+        vendor: market.vendor,
+        application,
+        authority: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+        config: configPda,
+      })
+      .rpc()
+    await program.methods
+      .approveVendor()
+      .accountsPartial({
+        marketplace,
+        vendor: market.vendor,
+        application,
+        collection: (await program.account.vendor.fetch(market.vendor)).collection,
+        authority: curator.publicKey,
+        systemProgram: SystemProgram.programId,
+        mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        config: configPda,
+      })
+      .signers([curator])
+      .rpc()
+
+    await program.methods
+      .createEquipmentCategory('irrigation', 'Irrigation')
+      .accountsPartial({
+        marketplace,
+        category,
+        authority: curator.publicKey,
+        systemProgram: SystemProgram.programId,
+        config: configPda,
+      })
+      .signers([curator])
+      .rpc()
+  })
+
+  afterAll(async () => {
+    await teardownMarket(market)
+  })
+
+  it('get_equipment_by_category pages the listings of a category and filters by tag', async () => {
+    const drip = await market.uploadEquipment(2, { category, categoryPage: categoryPage(0), tags: ['drip'] })
+    const pivot = await market.uploadEquipment(1, { category, categoryPage: categoryPage(0), tags: ['pivot'] })
+    await market.uploadEquipment(1)
+
+    const all = await readPage([drip, pivot])
+    expect(all.listingCount.toNumber()).toBe(2)
+    expect(all.equipment.map((listing) => listing.equipmentPda.toBase58())).toEqual([drip.toBase58(), pivot.toBase58()])
+    expect(all.equipment[0].availableForBnpl.toNumber()).toBe(2)
+    expect(all.failedLoads).toEqual([])
+
+    const tagged = await readPage([drip, pivot], 'pivot')
+    expect(tagged.equipment.map((listing) => listing.equipmentPda.toBase58())).toEqual([pivot.toBase58()])
+  })
+
+  it('upload_equipment rejects a category page without its category', async () => {
+    await expect(market.uploadEquipment(1, { categoryPage: categoryPage(0) })).rejects.toThrow(/InvalidCategory/)
+  })
+})
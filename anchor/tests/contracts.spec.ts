@@ -0,0 +1,34 @@
+import { Market, MIN_DEPOSIT, PRICE, program, setupMarket, teardownMarket } from './fixtures'
+
+describe('multi-unit contracts', () => {
+  let market: Market
+
+  beforeAll(async () => {
+    market = await setupMarket()
+  })
+
+  afterAll(async () => {
+    await teardownMarket(market)
+  })
+
+  it('create_contract reserves every unit of the order', async () => {
+    const equipment = await market.uploadEquipment(5)
+    const { contract } = await market.createContract(equipment, 3)
+
+    const order = await program.account.bnplContract.fetch(contract)
+    expect(order.quantity.toNumber()).toBe(3)
+    expect(order.totalAmount.eq(PRICE.muln(3))).toBe(true)
+    const listing = await program.account.equipment.fetch(equipment)
+    expect(listing.soldQuantity.toNumber()).toBe(3)
+    expect(listing.unitsIssued.toNumber()).toBe(3)
+  })
+
+  it('create_contract rejects a total below the listed price of every unit', async () => {
+    const equipment = await market.uploadEquipment(5)
+
+    await expect(
+      market.createContract(equipment, 2, { totalAmount: PRICE.muln(2).subn(1), deposit: MIN_DEPOSIT.muln(2) })
+    ).rejects.toThrow(/ContractTotalTooLow/)
+    expect((await program.account.equipment.fetch(equipment)).soldQuantity.toNumber()).toBe(0)
+  })
+})
@@ -0,0 +1,53 @@
+import { BN } from '@coral-xyz/anchor'
+import { Keypair } from '@solana/web3.js'
+import { FunderCriteria, Market, OPEN_CRITERIA, payer, program, setupMarket, teardownMarket } from './fixtures'
+
+describe('funder criteria', () => {
+  let market: Market
+
+  beforeAll(async () => {
+    market = await setupMarket()
+  })
+
+  afterAll(async () => {
+    await teardownMarket(market)
+  })
+
+  async function fundedListing(criteria: FunderCriteria) {
+    const equipment = await market.uploadEquipment(2)
+    await market.fundListing(equipment, 1, criteria)
+    return equipment
+  }
+
+  it('create_contract rejects a buyer below the minimum credit score', async () => {
+    // A fresh borrower profile starts with a score of zero.
+    const equipment = await fundedListing({ ...OPEN_CRITERIA, minCreditScore: new BN(1) })
+
+    await expect(market.createContract(equipment, 1, { funder: payer.publicKey })).rejects.toThrow(/CreditScoreTooLow/)
+  })
+
+  it('create_contract rejects a buyer without the required KYC attestation', async () => {
+    const equipment = await fundedListing({ ...OPEN_CRITERIA, kycAttestor: Keypair.generate().publicKey })
+
+    await expect(market.createContract(equipment, 1, { funder: payer.publicKey })).rejects.toThrow(
+      /KycAttestationRequired/
+    )
+  })
+
+  it('create_contract enforces the maximum number of open loans', async () => {
+    const equipment = await fundedListing({ ...OPEN_CRITERIA, maxTotalLoans: new BN(1) })
+    expect(await market.totalLoans()).toBe(0)
+
+    await market.createContract(await market.uploadEquipment(1), 1)
+    await expect(market.createContract(equipment, 1, { funder: payer.publicKey })).rejects.toThrow(/TooManyLoans/)
+  })
+
+  it('create_contract accepts a buyer who meets the criteria', async () => {
+    const equipment = await fundedListing({ ...OPEN_CRITERIA, maxTotalLoans: new BN(2) })
+
+    const { contract } = await market.createContract(equipment, 1, { funder: payer.publicKey })
+
+    expect((await program.account.bnplContract.fetch(contract)).payee.equals(payer.publicKey)).toBe(true)
+    expect(await market.totalLoans()).toBe(2)
+  })
+})
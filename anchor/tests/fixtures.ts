@@ -0,0 +1,316 @@
+import * as anchor from '@coral-xyz/anchor'
+import { BN, Program } from '@coral-xyz/anchor'
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from '@solana/spl-token'
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, Transaction } from '@solana/web3.js'
+import { Partpay } from '../target/types/partpay'
+
+export const provider = anchor.AnchorProvider.env()
+anchor.setProvider(provider)
+export const payer = provider.wallet as anchor.Wallet
+export const program = anchor.workspace.Partpaybuild as Program<Partpay>
+export const connection = provider.connection
+
+export const MPL_CORE_PROGRAM_ID = new PublicKey('CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d')
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111')
+export const PRICE = new BN(1_000_000)
+export const MIN_DEPOSIT = new BN(100_000)
+export const MAX_DURATION = new BN(30 * 24 * 60 * 60)
+
+export const pda = (seeds: (Buffer | Uint8Array)[]) =>
+  PublicKey.findProgramAddressSync(seeds, program.programId)[0]
+export const u64 = (value: number | BN) => new BN(value).toArrayLike(Buffer, 'le', 8)
+export const writable = (pubkey: PublicKey) => ({ pubkey, isSigner: false, isWritable: true })
+
+export const configPda = pda([Buffer.from('program_config')])
+
+export type FunderCriteria = { minCreditScore: BN; maxTotalLoans: BN | null; kycAttestor: PublicKey | null }
+export const OPEN_CRITERIA: FunderCriteria = { minCreditScore: new BN(0), maxTotalLoans: null, kycAttestor: null }
+
+export type Market = Awaited<ReturnType<typeof setupMarket>>
+
+/**
+ * Sets up a fresh allowed mint, a funded buyer with a borrower profile and a
+ * vendor, and returns helpers bound to them. Specs share the program config,
+ * so `teardownMarket` takes the mint back off the allow list. With
+ * `transferFeeBps` the mint is a Token-2022 mint with a transfer fee.
+ */
+export async function setupMarket({ transferFeeBps }: { transferFeeBps?: number } = {}) {
+  const tokenProgram = transferFeeBps === undefined ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID
+  const buyer = Keypair.generate()
+  const borrowerPda = pda([Buffer.from('borrower'), buyer.publicKey.toBuffer()])
+  const creditScorePda = pda([Buffer.from('credit_score'), borrowerPda.toBuffer()])
+
+  if (!(await connection.getAccountInfo(configPda))) {
+    await program.methods
+      .initializeConfig()
+      .accountsPartial({
+        config: configPda,
+        program: program.programId,
+        programData: PublicKey.findProgramAddressSync([program.programId.toBuffer()], BPF_LOADER_UPGRADEABLE_ID)[0],
+        admin: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc()
+  }
+
+  const mint =
+    transferFeeBps === undefined
+      ? await createMint(connection, payer.payer, payer.publicKey, null, 6)
+      : await createTransferFeeMint(transferFeeBps)
+  await program.methods.addAllowedMint(mint).accountsPartial({ config: configPda, admin: payer.publicKey }).rpc()
+
+  await provider.sendAndConfirm(
+    new Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: payer.publicKey,
+        toPubkey: buyer.publicKey,
+        lamports: 0.5 * LAMPORTS_PER_SOL,
+      })
+    )
+  )
+  const tokenAccount = async (owner: PublicKey) =>
+    (await getOrCreateAssociatedTokenAccount(connection, payer.payer, mint, owner, true, undefined, undefined, tokenProgram))
+      .address
+  const buyerTokenAccount = await tokenAccount(buyer.publicKey)
+  const payerTokenAccount = await tokenAccount(payer.publicKey)
+  await mintTo(connection, payer.payer, mint, buyerTokenAccount, payer.publicKey, 100_000_000, [], undefined, tokenProgram)
+  await mintTo(connection, payer.payer, mint, payerTokenAccount, payer.publicKey, 100_000_000, [], undefined, tokenProgram)
+
+  const vendorUniqueId = Keypair.generate().publicKey
+  const collectionUniqueId = Keypair.generate().publicKey
+  const vendor = pda([Buffer.from('vendor'), payer.publicKey.toBuffer(), vendorUniqueId.toBuffer()])
+  await program.methods
+    .createVendor('Heavy Kit', 'https://example.com/vendor.json', vendorUniqueId, collectionUniqueId)
+    .accountsPartial({
+      vendor,
+      vendorCollection: pda([Buffer.from('vendor_collection'), vendor.toBuffer(), collectionUniqueId.toBuffer()]),
+      authority: payer.publicKey,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+      mplCoreProgram: MPL_CORE_PROGRAM_ID,
+      config: configPda,
+    })
+    .rpc()
+  const vendorTokenAccount = await tokenAccount(vendor)
+
+  await program.methods
+    .initializeBorrower()
+    .accountsPartial({
+      borrower: borrowerPda,
+      authority: buyer.publicKey,
+      creditScore: creditScorePda,
+      systemProgram: SystemProgram.programId,
+      config: configPda,
+    })
+    .signers([buyer])
+    .rpc()
+
+  const balance = async (account: PublicKey) =>
+    new BN((await getAccount(connection, account, undefined, tokenProgram)).amount.toString())
+  const escrowAta = (owner: PublicKey) => getAssociatedTokenAddressSync(mint, owner, true, tokenProgram)
+  const totalLoans = async () => (await program.account.borrower.fetch(borrowerPda)).totalLoans.toNumber()
+
+  async function uploadEquipment(
+    totalQuantity: number,
+    {
+      category = null as PublicKey | null,
+      categoryPage = null as PublicKey | null,
+      tags = [] as string[],
+    } = {}
+  ): Promise<PublicKey> {
+    const vendorAccount = await program.account.vendor.fetch(vendor)
+    const equipment = pda([Buffer.from('equipment'), vendor.toBuffer(), u64(vendorAccount.catalogSlots)])
+    const page = Buffer.alloc(4)
+    page.writeUInt32LE(vendorAccount.catalogSlots.divn(64).toNumber())
+    await program.methods
+      .uploadEquipment(
+        'Excavator',
+        'https://example.com/excavator.json',
+        PRICE,
+        new BN(totalQuantity),
+        Keypair.generate().publicKey,
+        MIN_DEPOSIT,
+        MAX_DURATION,
+        { part: {} },
+        null,
+        tags
+      )
+      .accountsPartial({
+        equipment,
+        equipmentAsset: pda([Buffer.from('equipment_asset'), equipment.toBuffer()]),
+        vendor,
+        catalogPage: pda([Buffer.from('vendor_catalog_page'), vendor.toBuffer(), page]),
+        category,
+        categoryPage,
+        vendorCollection: vendorAccount.collection,
+        authority: payer.publicKey,
+        vendorDelegate: null,
+        payer: payer.publicKey,
+        usdcMint: mint,
+        currencyFeed: null,
+        systemProgram: SystemProgram.programId,
+        mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        config: configPda,
+      })
+      .rpc()
+    return equipment
+  }
+
+  async function createContract(
+    equipment: PublicKey,
+    quantity: number,
+    { totalAmount = PRICE.muln(quantity), deposit = MIN_DEPOSIT.muln(quantity), funder = null as PublicKey | null } = {}
+  ) {
+    const uniqueId = Keypair.generate().publicKey
+    const contract = pda([
+      Buffer.from('bnpl_contract'),
+      buyer.publicKey.toBuffer(),
+      equipment.toBuffer(),
+      uniqueId.toBuffer(),
+    ])
+    const escrow = pda([Buffer.from('escrow'), equipment.toBuffer(), buyer.publicKey.toBuffer(), uniqueId.toBuffer()])
+    await program.methods
+      .createContract(uniqueId, totalAmount, { weekly: {} }, deposit, null, funder, 0, new BN(quantity))
+      .accountsPartial({
+        contract,
+        equipment,
+        vendor,
+        buyer: buyer.publicKey,
+        usdcMint: mint,
+        buyerTokenAccount,
+        escrow,
+        escrowTokenAccount: escrowAta(escrow),
+        payeeTokenAccount: funder ? payerTokenAccount : vendorTokenAccount,
+        payee: funder ?? vendor,
+        borrower: borrowerPda,
+        creditScore: creditScorePda,
+        kycAttestation: null,
+        marketplace: null,
+        treasuryTokenAccount: null,
+        currencyFeed: null,
+        priceFeed: null,
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        config: configPda,
+      })
+      .signers([buyer])
+      .rpc()
+    return { contract, escrow }
+  }
+
+  async function makePayment(contract: PublicKey, equipment: PublicKey, amount: BN) {
+    await program.methods
+      .makePayment(amount)
+      .accountsPartial({
+        contract,
+        equipment,
+        buyer: buyer.publicKey,
+        usdcMint: mint,
+        buyerTokenAccount,
+        payeeTokenAccount: vendorTokenAccount,
+        vendor,
+        marketplace: null,
+        treasuryTokenAccount: null,
+        borrowerProfile: borrowerPda,
+        creditScore: creditScorePda,
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        config: configPda,
+      })
+      .signers([buyer])
+      .rpc()
+  }
+
+  /** Funds units of a listing from the payer, who signs as the funder. */
+  async function fundListing(equipment: PublicKey, quantity: number, criteria: FunderCriteria = OPEN_CRITERIA) {
+    const uniqueId = Keypair.generate().publicKey
+    const escrow = pda([Buffer.from('escrow'), equipment.toBuffer(), payer.publicKey.toBuffer(), uniqueId.toBuffer()])
+    await program.methods
+      .fundEquipmentForListing(new BN(quantity), MIN_DEPOSIT, MAX_DURATION, uniqueId, criteria, { markup: { bps: 0 } }, 0)
+      .accountsPartial({
+        escrow,
+        escrowTokenAccount: escrowAta(escrow),
+        equipment,
+        vendor,
+        funder: payer.publicKey,
+        usdcMint: mint,
+        marketplace: null,
+        treasuryTokenAccount: null,
+        funderTokenAccount: payerTokenAccount,
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        config: configPda,
+      })
+      .rpc()
+    return { uniqueId, escrow }
+  }
+
+  return {
+    mint,
+    tokenProgram,
+    buyer,
+    vendor,
+    vendorTokenAccount,
+    buyerTokenAccount,
+    payerTokenAccount,
+    borrowerPda,
+    creditScorePda,
+    balance,
+    escrowAta,
+    totalLoans,
+    uploadEquipment,
+    createContract,
+    makePayment,
+    fundListing,
+  }
+}
+
+async function createTransferFeeMint(transferFeeBps: number): Promise<PublicKey> {
+  const mint = Keypair.generate()
+  const space = getMintLen([ExtensionType.TransferFeeConfig])
+  await provider.sendAndConfirm(
+    new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: payer.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports: await connection.getMinimumBalanceForRentExemption(space),
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mint.publicKey,
+        payer.publicKey,
+        payer.publicKey,
+        transferFeeBps,
+        BigInt(1_000_000_000_000),
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(mint.publicKey, 6, payer.publicKey, null, TOKEN_2022_PROGRAM_ID)
+    ),
+    [mint]
+  )
+  return mint.publicKey
+}
+
+export async function teardownMarket(market: Market) {
+  await program.methods
+    .removeAllowedMint(market.mint)
+    .accountsPartial({ config: configPda, admin: payer.publicKey })
+    .rpc()
+}
@@ -0,0 +1,74 @@
+import { BN } from '@coral-xyz/anchor'
+import { PublicKey } from '@solana/web3.js'
+import { Market, PRICE, configPda, payer, program, setupMarket, teardownMarket } from './fixtures'
+
+describe('funder repricing', () => {
+  const newPrice = new BN(800_000)
+  let market: Market
+
+  beforeAll(async () => {
+    market = await setupMarket()
+  })
+
+  afterAll(async () => {
+    await teardownMarket(market)
+  })
+
+  async function reprice(equipment: PublicKey, uniqueId: PublicKey, escrow: PublicKey) {
+    await program.methods
+      .updateEquipmentVariant(0, null, newPrice, null)
+      .accountsPartial({
+        equipment,
+        vendor: market.vendor,
+        authority: payer.publicKey,
+        vendorDelegate: null,
+        config: configPda,
+      })
+      .rpc()
+
+    const before = await market.balance(market.payerTokenAccount)
+    await program.methods
+      .repriceFunderPosition(payer.publicKey, uniqueId)
+      .accountsPartial({
+        equipment,
+        vendor: market.vendor,
+        escrow,
+        escrowTokenAccount: market.escrowAta(escrow),
+        funderTokenAccount: market.payerTokenAccount,
+        signer: payer.publicKey,
+        usdcMint: market.mint,
+        tokenProgram: market.tokenProgram,
+        config: configPda,
+      })
+      .rpc()
+    return (await market.balance(market.payerTokenAccount)).sub(before)
+  }
+
+  it('reprice_funder_position refunds the funder after a price drop', async () => {
+    const equipment = await market.uploadEquipment(4)
+    const { uniqueId, escrow } = await market.fundListing(equipment, 2)
+
+    const refund = await reprice(equipment, uniqueId, escrow)
+
+    expect(refund.eq(PRICE.sub(newPrice).muln(2))).toBe(true)
+    expect((await program.account.escrow.fetch(escrow)).amount.eq(PRICE.muln(2).sub(refund))).toBe(true)
+    const [position] = (await program.account.equipment.fetch(equipment)).funders
+    expect(position.unitPrice.eq(newPrice)).toBe(true)
+    expect(position.amountFunded.eq(newPrice.muln(2))).toBe(true)
+  })
+
+  it('reprice_funder_position keeps sold units at their sale price', async () => {
+    const equipment = await market.uploadEquipment(4)
+    const { uniqueId, escrow } = await market.fundListing(equipment, 2)
+    await market.createContract(equipment, 1, { funder: payer.publicKey })
+
+    const refund = await reprice(equipment, uniqueId, escrow)
+
+    // Only the unsold unit is repriced; the sold one stays financed at PRICE.
+    expect(refund.eq(PRICE.sub(newPrice))).toBe(true)
+    const [position] = (await program.account.equipment.fetch(equipment)).funders
+    expect(position.unitPrice.eq(newPrice)).toBe(true)
+    expect(position.soldPrincipal.eq(PRICE)).toBe(true)
+    expect(position.amountFunded.eq(PRICE.add(newPrice))).toBe(true)
+  })
+})
@@ -0,0 +1,96 @@
+import { SystemProgram } from '@solana/web3.js'
+import { MPL_CORE_PROGRAM_ID, Market, configPda, payer, pda, program, setupMarket, teardownMarket, u64 } from './fixtures'
+
+describe('order lifecycle', () => {
+  let market: Market
+
+  beforeAll(async () => {
+    market = await setupMarket()
+  })
+
+  afterAll(async () => {
+    await teardownMarket(market)
+  })
+
+  it('cancel_order refunds the deposit and puts the units back on sale', async () => {
+    const { buyer, mint, vendor, buyerTokenAccount, borrowerPda } = market
+    const equipment = await market.uploadEquipment(3)
+    const before = await market.balance(buyerTokenAccount)
+    const { contract, escrow } = await market.createContract(equipment, 2)
+    expect(await market.totalLoans()).toBe(1)
+
+    await program.methods
+      .cancelOrder()
+      .accountsPartial({
+        contract,
+        equipment,
+        vendor,
+        escrow,
+        escrowTokenAccount: market.escrowAta(escrow),
+        borrowerTokenAccount: buyerTokenAccount,
+        borrowerProfile: borrowerPda,
+        authority: buyer.publicKey,
+        vendorDelegate: null,
+        usdcMint: mint,
+        tokenProgram: market.tokenProgram,
+        config: configPda,
+      })
+      .signers([buyer])
+      .rpc()
+
+    expect((await market.balance(buyerTokenAccount)).eq(before)).toBe(true)
+    const listing = await program.account.equipment.fetch(equipment)
+    expect(listing.soldQuantity.toNumber()).toBe(0)
+    expect(listing.openEscrows.toNumber()).toBe(0)
+    expect(listing.activeContracts.toNumber()).toBe(0)
+    // Unit indices are never handed out twice, even after a cancellation.
+    expect(listing.unitsIssued.toNumber()).toBe(2)
+    expect((await program.account.bnplContract.fetch(contract)).deliveryStatus).toEqual({ cancelled: {} })
+    expect(await market.totalLoans()).toBe(0)
+  })
+
+  it('resolve_dispute refunds the borrower and unwinds the order', async () => {
+    const { buyer, mint, vendor, buyerTokenAccount, vendorTokenAccount, borrowerPda } = market
+    const equipment = await market.uploadEquipment(2)
+    const before = await market.balance(buyerTokenAccount)
+    const { contract, escrow } = await market.createContract(equipment, 1)
+
+    await program.methods
+      .openDispute()
+      .accountsPartial({ contract, borrower: buyer.publicKey, config: configPda })
+      .signers([buyer])
+      .rpc()
+    await program.methods
+      .resolveDispute(true)
+      .accountsPartial({
+        contract,
+        equipment,
+        vendor,
+        escrow,
+        escrowTokenAccount: market.escrowAta(escrow),
+        borrower: buyer.publicKey,
+        borrowerTokenAccount: buyerTokenAccount,
+        payeeTokenAccount: vendorTokenAccount,
+        borrowerProfile: borrowerPda,
+        unitAsset: pda([Buffer.from('unit_asset'), contract.toBuffer(), u64(0)]),
+        marketplace: null,
+        resolver: payer.publicKey,
+        usdcMint: mint,
+        tokenProgram: market.tokenProgram,
+        systemProgram: SystemProgram.programId,
+        mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        config: configPda,
+      })
+      .rpc()
+
+    expect((await market.balance(buyerTokenAccount)).eq(before)).toBe(true)
+    expect((await program.account.escrow.fetch(escrow)).isReleased).toBe(true)
+    const listing = await program.account.equipment.fetch(equipment)
+    expect(listing.soldQuantity.toNumber()).toBe(0)
+    expect(listing.activeContracts.toNumber()).toBe(0)
+    const order = await program.account.bnplContract.fetch(contract)
+    expect(order.isCompleted).toBe(true)
+    expect(order.deliveryStatus).toEqual({ cancelled: {} })
+    expect(await market.totalLoans()).toBe(0)
+  })
+})
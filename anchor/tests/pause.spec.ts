@@ -0,0 +1,44 @@
+import { BN } from '@coral-xyz/anchor'
+import { Keypair } from '@solana/web3.js'
+import { Market, configPda, payer, program, setupMarket, teardownMarket } from './fixtures'
+
+// Mirrors `ProgramConfig::PAUSE_CONTRACTS`.
+const PAUSE_CONTRACTS = 1 << 2
+
+describe('emergency pause', () => {
+  let market: Market
+
+  const setPaused = (paused: number, admin = payer.payer) =>
+    program.methods.setPaused(paused).accountsPartial({ config: configPda, admin: admin.publicKey }).signers([admin]).rpc()
+
+  beforeAll(async () => {
+    market = await setupMarket()
+  })
+
+  afterAll(async () => {
+    await setPaused(0)
+    await teardownMarket(market)
+  })
+
+  it('set_paused is limited to the config admin', async () => {
+    await expect(setPaused(PAUSE_CONTRACTS, Keypair.generate())).rejects.toThrow(/InvalidAdmin/)
+    expect((await program.account.programConfig.fetch(configPda)).paused).toBe(0)
+  })
+
+  it('pausing contracts blocks new contracts but not repayments', async () => {
+    const equipment = await market.uploadEquipment(2)
+    const { contract } = await market.createContract(equipment, 1)
+
+    await setPaused(PAUSE_CONTRACTS)
+    try {
+      await expect(market.createContract(equipment, 1)).rejects.toThrow(/OperationPaused/)
+      await market.makePayment(contract, equipment, new BN(100_000))
+    } finally {
+      await setPaused(0)
+    }
+
+    const order = await program.account.bnplContract.fetch(contract)
+    expect(order.amountPaid.eq(new BN(200_000))).toBe(true)
+    await market.createContract(equipment, 1)
+  })
+})
@@ -0,0 +1,46 @@
+import { BN } from '@coral-xyz/anchor'
+import { Market, program, setupMarket, teardownMarket } from './fixtures'
+
+describe('transfer-fee stablecoins', () => {
+  const FEE_BPS = 100
+  let market: Market
+
+  // Smallest amount that still delivers `net` after the mint's fee, as the
+  // program's `gross_up_for_transfer_fee` computes it.
+  const grossUp = (net: BN) => net.muln(10_000).addn(10_000 - FEE_BPS - 1).divn(10_000 - FEE_BPS)
+
+  beforeAll(async () => {
+    market = await setupMarket({ transferFeeBps: FEE_BPS })
+  })
+
+  afterAll(async () => {
+    await teardownMarket(market)
+  })
+
+  it('create_contract credits the deposit that reached escrow', async () => {
+    const equipment = await market.uploadEquipment(2)
+    const { contract, escrow } = await market.createContract(equipment, 1)
+
+    const order = await program.account.bnplContract.fetch(contract)
+    const escrowed = await market.balance(market.escrowAta(escrow))
+    expect(escrowed.lt(order.deposit)).toBe(true)
+    expect((await program.account.escrow.fetch(escrow)).amount.eq(escrowed)).toBe(true)
+    expect(order.amountPaid.eq(escrowed)).toBe(true)
+  })
+
+  it('make_payment accepts a grossed-up final payment and completes the contract', async () => {
+    const equipment = await market.uploadEquipment(2)
+    const { contract } = await market.createContract(equipment, 1)
+    const created = await program.account.bnplContract.fetch(contract)
+    const remaining = created.totalAmount.sub(created.amountPaid)
+    const vendorBefore = await market.balance(market.vendorTokenAccount)
+
+    await expect(market.makePayment(contract, equipment, grossUp(remaining).addn(1))).rejects.toThrow(/Overpayment/)
+    await market.makePayment(contract, equipment, grossUp(remaining))
+
+    const order = await program.account.bnplContract.fetch(contract)
+    expect(order.isCompleted).toBe(true)
+    expect(order.amountPaid.eq(order.totalAmount)).toBe(true)
+    expect((await market.balance(market.vendorTokenAccount)).sub(vendorBefore).eq(remaining)).toBe(true)
+  })
+})